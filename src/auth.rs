@@ -1,10 +1,10 @@
 //! Authentication towards the API.
-use std::sync::{Arc, Mutex, MutexGuard};
+use std::sync::{Arc, Mutex};
 
 use crate::reddit::{Error, Result};
 
 use reqwest::{
-    blocking::{Client, Response},
+    blocking::{Client, RequestBuilder, Response},
     header, StatusCode,
 };
 use serde::{Deserialize, Serialize};
@@ -35,28 +35,47 @@ pub trait Authenticator {
 pub struct Token {
     pub access_token: String,
     pub expires_in: i32,
+    #[allow(dead_code)]
     scope: String,
+    #[allow(dead_code)]
     token_type: String,
 }
 
 /// Authenticated interaction with the Reddit API. Use [`crate::reddit::Reddit`] instead.
 /// This is shared by all current interactors with what reddit calls 'things', so they can make requests for more posts, comments, etc.
-#[derive(Debug, Clone)]
+/// Cloning is cheap, and the clones share the connection and the token.
+#[derive(Debug)]
 pub struct AuthenticatedClient<T: Authenticator> {
     pub(crate) client: Arc<Mutex<Client>>,
     pub(crate) authenticator: Arc<Mutex<T>>,
+    // Without the access token, for media hosted outside the API.
+    download_client: Arc<Client>,
     user_agent: String,
 }
 
+// Derived Clone would require T: Clone, but only the Arcs are cloned.
+impl<T: Authenticator> Clone for AuthenticatedClient<T> {
+    fn clone(&self) -> Self {
+        Self {
+            client: Arc::clone(&self.client),
+            authenticator: Arc::clone(&self.authenticator),
+            download_client: Arc::clone(&self.download_client),
+            user_agent: self.user_agent.clone(),
+        }
+    }
+}
+
 impl<T: Authenticator> AuthenticatedClient<T> {
     pub fn new(mut authenticator: T, user_agent: &str) -> Result<Self> {
         authenticator.login()?;
 
         if let Some(token) = authenticator.token() {
             let client = Self::make_client(user_agent, &token.access_token)?;
+            let download_client = Client::builder().user_agent(user_agent).build()?;
             Ok(Self {
                 authenticator: Arc::new(Mutex::new(authenticator)),
                 client: Arc::new(Mutex::new(client)),
+                download_client: Arc::new(download_client),
                 user_agent: String::from(user_agent),
             })
         } else {
@@ -65,39 +84,84 @@ impl<T: Authenticator> AuthenticatedClient<T> {
         }
     }
 
-    /// Make a get request to `url`
+    /// Make a get request to `url`. Always asks Reddit for unescaped JSON, with `raw_json=1`.
     /// Errors if the status code was unexpected, the client cannot re-initialize or make the request, or if the authentication fails.
     pub fn get<Q: Serialize>(&self, url: &str, queries: Option<&Q>) -> Result<Response> {
-        // Make one request
-        let mut client = self
-            .client
+        self.send(|client| {
+            // Otherwise Reddit escapes &, < and > in all strings.
+            let request = client.get(url).query(&[("raw_json", "1")]);
+
+            if let Some(queries) = queries {
+                request.query(queries)
+            } else {
+                request
+            }
+        })
+    }
+
+    /// Make a post request to `url`, with `form` as the form encoded body.
+    /// Errors if the status code was unexpected, the client cannot re-initialize or make the request, or if the authentication fails.
+    pub fn post<F: Serialize + ?Sized>(&self, url: &str, form: &F) -> Result<Response> {
+        self.send(|client| client.post(url).form(form))
+    }
+
+    /// Make a put request to `url`, with `form` as the form encoded body.
+    /// Errors if the status code was unexpected, the client cannot re-initialize or make the request, or if the authentication fails.
+    pub fn put<F: Serialize + ?Sized>(&self, url: &str, form: &F) -> Result<Response> {
+        self.send(|client| client.put(url).form(form))
+    }
+
+    /// Make a patch request to `url`, with `json` as the JSON body.
+    /// Errors if the status code was unexpected, the client cannot re-initialize or make the request, or if the authentication fails.
+    pub fn patch<B: Serialize + ?Sized>(&self, url: &str, json: &B) -> Result<Response> {
+        self.send(|client| client.patch(url).json(json))
+    }
+
+    /// Make a delete request to `url`.
+    /// Errors if the status code was unexpected, the client cannot re-initialize or make the request, or if the authentication fails.
+    pub fn delete(&self, url: &str) -> Result<Response> {
+        self.send(|client| client.delete(url))
+    }
+
+    /// Download `url` without the access token, e.g. images and videos hosted outside the API.
+    /// Errors if the request fails or the status code is not successful.
+    pub fn download(&self, url: &str) -> Result<Vec<u8>> {
+        let response = self.download_client.get(url).send()?.error_for_status()?;
+
+        Ok(response.bytes()?.to_vec())
+    }
+
+    /// Whether the authenticator is logged in as a user. See [`Authenticator::is_user`].
+    pub fn is_user(&self) -> bool {
+        self.authenticator
             .lock()
-            .expect("Poisoned mutex, report bug at https://github.com/Zower/snew");
+            .expect("Poisoned mutex, report bug at https://github.com/Zower/snew")
+            .is_user()
+    }
 
-        let response = self.make_request(&client, url, queries)?;
+    // Errors with NotLoggedInError if the authenticator is not logged in as a user.
+    pub(crate) fn ensure_user(&self) -> Result<()> {
+        if self.is_user() {
+            Ok(())
+        } else {
+            Err(Error::NotLoggedInError)
+        }
+    }
+
+    // Builds and sends the request, refreshing the token and retrying once if reddit refuses it.
+    // The locks are only held to read or replace the client, never during a request, so other threads are not blocked.
+    fn send<F: Fn(&Client) -> RequestBuilder>(&self, build: F) -> Result<Response> {
+        // Make one request
+        let response = build(&self.current_client()).send()?;
 
         // Check if the request was successful
         if self.check_auth(&response)? {
             Ok(response)
         } else {
-            // Refresh token
-            let mut authenticator = self
-                .authenticator
-                .lock()
-                .expect("Poisoned mutex, report bug at https://github.com/Zower/snew");
-            authenticator.login()?;
-
-            if let Some(token) = authenticator.token() {
-                // Create a new client with correct token
-                *client = Self::make_client(&self.user_agent, &token.access_token)?;
-            } else {
-                // Pretty sure this can never happen, but better safe than sorry? :D
-                return Err(Error::AuthenticationError(String::from("Token was not set after logging in, but no error was returned. Report bug at https://github.com/Zower/snew")));
-            }
+            let client = self.refresh_client()?;
+            let response = build(&client).send()?;
 
-            let response = self.make_request(&client, url, queries)?;
-
-            if response.status() == StatusCode::OK {
+            if self.check_auth(&response)? {
                 Ok(response)
             }
             // Still not authenticated correctly
@@ -109,33 +173,61 @@ impl<T: Authenticator> AuthenticatedClient<T> {
         }
     }
 
-    // Checks queries and makes the actual web request
-    fn make_request<Q: Serialize>(
-        &self,
-        client: &MutexGuard<Client>,
-        url: &str,
-        queries: Option<&Q>,
-    ) -> Result<Response> {
-        if let Some(queries) = queries {
-            Ok(client.get(url).query(queries).send()?)
-        } else {
-            Ok(client.get(url).send()?)
-        }
+    // Cloning the client is cheap, it shares the connection pool.
+    fn current_client(&self) -> Client {
+        self.client
+            .lock()
+            .expect("Poisoned mutex, report bug at https://github.com/Zower/snew")
+            .clone()
+    }
+
+    // Logs in again and replaces the client with one using the new token.
+    fn refresh_client(&self) -> Result<Client> {
+        let token = {
+            let mut authenticator = self
+                .authenticator
+                .lock()
+                .expect("Poisoned mutex, report bug at https://github.com/Zower/snew");
+            authenticator.login()?;
+
+            match authenticator.token() {
+                Some(token) => token.access_token,
+                // Pretty sure this can never happen, but better safe than sorry? :D
+                None => return Err(Error::AuthenticationError(String::from("Token was not set after logging in, but no error was returned. Report bug at https://github.com/Zower/snew"))),
+            }
+        };
+
+        // Create a new client with correct token
+        let client = Self::make_client(&self.user_agent, &token)?;
+        *self
+            .client
+            .lock()
+            .expect("Poisoned mutex, report bug at https://github.com/Zower/snew") = client.clone();
+
+        Ok(client)
     }
 
-    // Checks that the response is OK. Errors if status code is not expected.
+    // Checks that the response is OK. False if the token was not accepted, and a new one is needed.
+    // Errors if status code is not expected.
     fn check_auth(&self, response: &Response) -> Result<bool> {
         let status = response.status();
 
-        if status == StatusCode::OK {
+        // Some endpoints respond with e.g. 201 Created
+        if status.is_success() {
             Ok(true)
-        } else if status == StatusCode::FORBIDDEN || status == StatusCode::UNAUTHORIZED {
+        } else if status == StatusCode::UNAUTHORIZED {
             Ok(false)
+        } else if status == StatusCode::FORBIDDEN {
+            // The token is fine, but not allowed to do this, e.g. a private subreddit or anonymous authentication.
+            Err(Error::APIError(format!(
+                "Reddit refused the request: {}",
+                status
+            )))
         } else {
-            return Err(Error::AuthenticationError(format!(
+            Err(Error::AuthenticationError(format!(
                 "Reddit returned an unexpected code: {}",
                 status
-            )));
+            )))
         }
     }
 
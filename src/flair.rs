@@ -0,0 +1,353 @@
+//! Flair of posts and users, and the flair templates of subreddits.
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    auth::{AuthenticatedClient, Authenticator},
+    reddit::{check_api_errors, Result},
+};
+
+/// Flair, i.e. a small label shown next to a post title, or next to the name of a user.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Flair {
+    pub text: Option<String>,
+    /// The CSS class, used by old reddit styles.
+    pub css_class: Option<String>,
+    /// The id of the template this flair is based on, if any.
+    pub template_id: Option<String>,
+    /// The flair as text and emojis. Empty if this is a plain text flair.
+    pub richtext: Vec<FlairRichtext>,
+    /// The background color, e.g. `#ff4500`.
+    pub background_color: Option<String>,
+    /// Either `dark` or `light`.
+    pub text_color: Option<String>,
+}
+
+/// A part of a richtext flair.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "e")]
+pub enum FlairRichtext {
+    #[serde(rename = "text")]
+    Text {
+        #[serde(rename = "t")]
+        text: String,
+    },
+    #[serde(rename = "emoji")]
+    Emoji {
+        /// The name of the emoji, e.g. `:rust:`.
+        #[serde(rename = "a")]
+        name: String,
+        /// The URL of the emoji image.
+        #[serde(rename = "u")]
+        url: String,
+    },
+    /// A kind of element not known to snew.
+    #[serde(other)]
+    Unknown,
+}
+
+/// A flair template of a subreddit, which posts or users can select. See [`SubredditFlair::link_templates`].
+#[derive(Debug, Clone, Deserialize)]
+pub struct FlairTemplate {
+    pub id: String,
+    #[serde(default)]
+    pub text: String,
+    /// Whether users can change the text when selecting this template.
+    pub text_editable: bool,
+    /// Whether only moderators can select this template.
+    #[serde(default)]
+    pub mod_only: bool,
+    #[serde(default, deserialize_with = "crate::things::raw::non_empty")]
+    pub css_class: Option<String>,
+    #[serde(default, deserialize_with = "crate::things::raw::non_empty")]
+    pub background_color: Option<String>,
+    /// Either `dark` or `light`.
+    pub text_color: Option<String>,
+    #[serde(default)]
+    pub richtext: Vec<FlairRichtext>,
+}
+
+/// The settings of a new or updated flair template. See [`SubredditFlair::create_link_template`].
+#[derive(Debug, Clone, Default)]
+pub struct FlairTemplateOptions {
+    pub text: String,
+    pub css_class: Option<String>,
+    /// The background color, e.g. `#ff4500`.
+    pub background_color: Option<String>,
+    /// Either `dark` or `light`.
+    pub text_color: Option<String>,
+    /// Whether users can change the text when selecting this template.
+    pub text_editable: bool,
+    /// Whether only moderators can select this template.
+    pub mod_only: bool,
+}
+
+/// The flair of one user, for setting the flair of many users at once. See [`SubredditFlair::bulk_set_user_flair`].
+#[derive(Debug, Clone, Default)]
+pub struct UserFlair {
+    pub username: String,
+    /// The flair text. If both this and the css class are None, the flair of the user is removed.
+    pub text: Option<String>,
+    pub css_class: Option<String>,
+}
+
+/// The result of setting the flair of one user. See [`SubredditFlair::bulk_set_user_flair`].
+#[derive(Debug, Clone, Deserialize)]
+pub struct FlairCsvResult {
+    pub ok: bool,
+    /// A description of what happened, e.g. `added flair for user someone`.
+    pub status: String,
+    #[serde(default)]
+    pub errors: HashMap<String, String>,
+    #[serde(default)]
+    pub warnings: HashMap<String, String>,
+}
+
+/// A handle to the flair of a subreddit. Use [`crate::things::Subreddit::flair`] to create one.
+/// Everything except listing templates and selecting flair requires the logged in user to moderate the subreddit.
+/// ```no_run
+/// # fn main() -> snew::reddit::Result<()> {
+/// # use snew::{reddit::Reddit, auth::{ScriptAuthenticator, Credentials}};
+/// # let reddit = Reddit::new(
+/// #    ScriptAuthenticator::new(Credentials::new("client_id", "client_secret", "username", "password")),
+/// #    "<Operating system>:snew:v0.1.0 (by u/<reddit username>)"
+/// #    ).unwrap();
+/// let flair = reddit.subreddit("mysubreddit").flair();
+///
+/// let solved = flair
+///     .link_templates()?
+///     .into_iter()
+///     .find(|template| template.text == "Solved");
+///
+/// if let Some(solved) = solved {
+///     flair.set_post_flair("t3_nrm0y9", &solved.id, None)?;
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct SubredditFlair<T: Authenticator> {
+    /// The URL of the subreddit.
+    pub url: String,
+    client: AuthenticatedClient<T>,
+}
+
+impl<T: Authenticator> SubredditFlair<T> {
+    /// Create a instance of subreddit flair
+    /// Use [`crate::things::Subreddit::flair()`] instead.
+    pub fn create(url: &str, client: &AuthenticatedClient<T>) -> Self {
+        Self {
+            url: String::from(url),
+            client: client.clone(),
+        }
+    }
+
+    /// The flair templates for posts.
+    pub fn link_templates(&self) -> Result<Vec<FlairTemplate>> {
+        self.templates("link_flair_v2")
+    }
+
+    /// The flair templates for users.
+    pub fn user_templates(&self) -> Result<Vec<FlairTemplate>> {
+        self.templates("user_flair_v2")
+    }
+
+    /// Create a new flair template for posts.
+    pub fn create_link_template(&self, options: &FlairTemplateOptions) -> Result<FlairTemplate> {
+        self.save_template("LINK_FLAIR", None, options)
+    }
+
+    /// Create a new flair template for users.
+    pub fn create_user_template(&self, options: &FlairTemplateOptions) -> Result<FlairTemplate> {
+        self.save_template("USER_FLAIR", None, options)
+    }
+
+    /// Replace the settings of an existing flair template for posts.
+    pub fn update_link_template(
+        &self,
+        template_id: &str,
+        options: &FlairTemplateOptions,
+    ) -> Result<FlairTemplate> {
+        self.save_template("LINK_FLAIR", Some(template_id), options)
+    }
+
+    /// Replace the settings of an existing flair template for users.
+    pub fn update_user_template(
+        &self,
+        template_id: &str,
+        options: &FlairTemplateOptions,
+    ) -> Result<FlairTemplate> {
+        self.save_template("USER_FLAIR", Some(template_id), options)
+    }
+
+    /// Delete a flair template, for either posts or users.
+    pub fn delete_template(&self, template_id: &str) -> Result<()> {
+        self.post(
+            "deleteflairtemplate",
+            &[("api_type", "json"), ("flair_template_id", template_id)],
+        )?;
+
+        Ok(())
+    }
+
+    /// Set the flair of a post to one of the templates. If the template is editable, `text` replaces the template text.
+    pub fn set_post_flair(
+        &self,
+        post_fullname: &str,
+        template_id: &str,
+        text: Option<&str>,
+    ) -> Result<()> {
+        let mut form = vec![
+            ("api_type", "json"),
+            ("link", post_fullname),
+            ("flair_template_id", template_id),
+        ];
+        form.extend(text.map(|text| ("text", text)));
+
+        self.post("selectflair", &form)?;
+
+        Ok(())
+    }
+
+    /// Set the flair of a user to one of the templates. If the template is editable, `text` replaces the template text.
+    pub fn select_user_flair(
+        &self,
+        username: &str,
+        template_id: &str,
+        text: Option<&str>,
+    ) -> Result<()> {
+        let mut form = vec![
+            ("api_type", "json"),
+            ("name", username),
+            ("flair_template_id", template_id),
+        ];
+        form.extend(text.map(|text| ("text", text)));
+
+        self.post("selectflair", &form)?;
+
+        Ok(())
+    }
+
+    /// Set the flair of a user to the given text and CSS class, without a template.
+    /// If both are None, the flair of the user is removed.
+    pub fn set_user_flair(
+        &self,
+        username: &str,
+        text: Option<&str>,
+        css_class: Option<&str>,
+    ) -> Result<()> {
+        self.post(
+            "flair",
+            &[
+                ("api_type", "json"),
+                ("name", username),
+                ("text", text.unwrap_or("")),
+                ("css_class", css_class.unwrap_or("")),
+            ],
+        )?;
+
+        Ok(())
+    }
+
+    /// Set the flair of many users at once, returning the result for each user in order.
+    /// Reddit accepts 100 users per request, so this makes one request per 100 users.
+    pub fn bulk_set_user_flair(&self, flairs: &[UserFlair]) -> Result<Vec<FlairCsvResult>> {
+        let mut results = Vec::with_capacity(flairs.len());
+
+        for chunk in flairs.chunks(100) {
+            let csv = chunk
+                .iter()
+                .map(|flair| {
+                    [
+                        Some(flair.username.as_str()),
+                        flair.text.as_deref(),
+                        flair.css_class.as_deref(),
+                    ]
+                    .iter()
+                    .map(|field| csv_field(field.unwrap_or("")))
+                    .collect::<Vec<_>>()
+                    .join(",")
+                })
+                .collect::<Vec<_>>()
+                .join("\n");
+
+            let text = self.post("flaircsv", &[("flair_csv", csv.as_str())])?;
+
+            results.extend(serde_json::from_str::<Vec<FlairCsvResult>>(&text)?);
+        }
+
+        Ok(results)
+    }
+
+    fn templates(&self, path: &str) -> Result<Vec<FlairTemplate>> {
+        let text = self
+            .client
+            .get(&format!("{}/api/{}", self.url, path), None::<&()>)?
+            .text()?;
+
+        Ok(serde_json::from_str(&text)?)
+    }
+
+    fn save_template(
+        &self,
+        flair_type: &str,
+        template_id: Option<&str>,
+        options: &FlairTemplateOptions,
+    ) -> Result<FlairTemplate> {
+        let mut form = vec![
+            ("api_type", "json"),
+            ("flair_type", flair_type),
+            ("text", options.text.as_str()),
+            ("text_editable", bool_str(options.text_editable)),
+            ("mod_only", bool_str(options.mod_only)),
+        ];
+
+        let optional = [
+            ("flair_template_id", template_id),
+            ("css_class", options.css_class.as_deref()),
+            ("background_color", options.background_color.as_deref()),
+            ("text_color", options.text_color.as_deref()),
+        ];
+
+        form.extend(
+            optional
+                .iter()
+                .filter_map(|(key, value)| value.map(|value| (*key, value))),
+        );
+
+        let text = self.post("flairtemplate_v2", &form)?;
+
+        Ok(serde_json::from_str(&text)?)
+    }
+
+    // Post to the flair API of this subreddit, returning the response text if Reddit did not report any errors.
+    fn post(&self, path: &str, form: &[(&str, &str)]) -> Result<String> {
+        self.client.ensure_user()?;
+
+        let text = self
+            .client
+            .post(&format!("{}/api/{}", self.url, path), form)?
+            .text()?;
+
+        // Bulk flair responds with a list of results, which never contains API errors.
+        if !text.trim_start().starts_with('[') {
+            check_api_errors(&text)?;
+        }
+
+        Ok(text)
+    }
+}
+
+fn bool_str(value: bool) -> &'static str {
+    if value {
+        "true"
+    } else {
+        "false"
+    }
+}
+
+// Quote a CSV field, escaping any quotes within it.
+pub(crate) fn csv_field(field: &str) -> String {
+    format!("\"{}\"", field.replace('"', "\"\""))
+}
@@ -0,0 +1,244 @@
+//! The inbox of the logged in user, i.e. private messages, comment replies and mentions.
+use std::time::SystemTime;
+
+use serde::Serialize;
+
+use crate::{
+    auth::{AuthenticatedClient, Authenticator},
+    reddit::{check_api_errors, Result, URL},
+    stream::Stream,
+    things::{
+        raw::{self, generic_kind::RawKind, message::RawMessageData},
+        Feed, FromListing, Fullname,
+    },
+};
+
+/// A handle to interact with the inbox of the logged in user.
+/// Use [`crate::reddit::Reddit::inbox`] to create one.
+#[derive(Debug)]
+pub struct Inbox<T: Authenticator> {
+    pub(crate) client: AuthenticatedClient<T>,
+}
+
+impl<T: Authenticator> Inbox<T> {
+    /// Create a instance of the inbox
+    /// Use [`crate::reddit::Reddit::inbox()`] instead.
+    pub fn create(client: &AuthenticatedClient<T>) -> Self {
+        Self {
+            client: client.clone(),
+        }
+    }
+
+    /// Everything in the inbox, i.e. messages, comment replies and mentions.
+    pub fn all(&self) -> Feed<T, Message<T>> {
+        self.listing("inbox")
+    }
+
+    /// Everything in the inbox that has not been marked as read.
+    pub fn unread(&self) -> Feed<T, Message<T>> {
+        self.listing("unread")
+    }
+
+    /// Private messages.
+    pub fn messages(&self) -> Feed<T, Message<T>> {
+        self.listing("messages")
+    }
+
+    /// Replies to comments made by the logged in user.
+    pub fn comment_replies(&self) -> Feed<T, Message<T>> {
+        self.listing("comments")
+    }
+
+    /// Top level comments on posts made by the logged in user.
+    pub fn selfreply(&self) -> Feed<T, Message<T>> {
+        self.listing("selfreply")
+    }
+
+    /// Comments mentioning the logged in user, i.e. containing u/username.
+    pub fn mentions(&self) -> Feed<T, Message<T>> {
+        self.listing("mentions")
+    }
+
+    /// Private messages sent by the logged in user.
+    pub fn sent(&self) -> Feed<T, Message<T>> {
+        self.listing("sent")
+    }
+
+    /// Unread messages, comment replies and mentions, as they arrive. See [`Stream`].
+    pub fn stream(&self) -> Stream<T, Message<T>> {
+        Stream::new(format!("{}message/unread", URL), &self.client)
+    }
+
+    /// Send a private message.
+    /// `to` is either a username, or a subreddit in the form `/r/name` to message its moderators.
+    pub fn compose(&self, to: &str, subject: &str, body: &str) -> Result<()> {
+        self.client.ensure_user()?;
+
+        let text = self
+            .client
+            .post(
+                &format!("{}api/compose", URL),
+                &[
+                    ("api_type", "json"),
+                    ("to", to),
+                    ("subject", subject),
+                    ("text", body),
+                ],
+            )?
+            .text()?;
+
+        check_api_errors(&text)
+    }
+
+    /// Mark everything in the inbox as read.
+    pub fn mark_all_read(&self) -> Result<()> {
+        self.client.ensure_user()?;
+
+        self.client
+            .post(&format!("{}api/read_all_messages", URL), &())?;
+
+        Ok(())
+    }
+
+    fn listing(&self, path: &str) -> Feed<T, Message<T>> {
+        Feed::new(format!("{}message/{}", URL, path), &self.client)
+    }
+}
+
+/// A private message, or a comment reply or mention as it appears in the inbox.
+/// Serializes without the client, in the same format Reddit uses.
+#[derive(Debug, Serialize)]
+pub struct Message<T: Authenticator> {
+    #[serde(skip)]
+    client: AuthenticatedClient<T>,
+    /// The unique base 36 ID of this message
+    pub id: String,
+    /// The fullname of this message, e.g. `t4_1a2b3c`, or `t1_1a2b3c` if this is a comment.
+    pub name: Fullname,
+    /// The author. None for messages sent on behalf of a subreddit.
+    pub author: Option<String>,
+    /// The recipient.
+    pub dest: String,
+    pub subject: String,
+    /// The text of this message.
+    pub body: String,
+    #[serde(serialize_with = "raw::timestamp::serialize")]
+    pub created_utc: SystemTime,
+    /// Whether this message is unread.
+    pub new: bool,
+    /// Whether this is a comment reply or mention, rather than a private message.
+    pub was_comment: bool,
+    /// The permalink of the comment, if this is a comment reply or mention. Empty otherwise.
+    pub context: String,
+    /// The fullname of the message or comment this is a reply to.
+    pub parent_id: Option<Fullname>,
+    /// The fullname of the first message in this conversation.
+    pub first_message_name: Option<Fullname>,
+    /// The subreddit of the comment, or the subreddit that sent or received this message.
+    pub subreddit: Option<String>,
+    /// Whether this message was sent as a moderator or admin.
+    pub distinguished: Option<String>,
+}
+
+impl<T: Authenticator> Clone for Message<T> {
+    fn clone(&self) -> Self {
+        Self {
+            client: self.client.clone(),
+            id: self.id.clone(),
+            name: self.name.clone(),
+            author: self.author.clone(),
+            dest: self.dest.clone(),
+            subject: self.subject.clone(),
+            body: self.body.clone(),
+            created_utc: self.created_utc,
+            new: self.new,
+            was_comment: self.was_comment,
+            context: self.context.clone(),
+            parent_id: self.parent_id.clone(),
+            first_message_name: self.first_message_name.clone(),
+            subreddit: self.subreddit.clone(),
+            distinguished: self.distinguished.clone(),
+        }
+    }
+}
+
+impl<T: Authenticator> Message<T> {
+    /// Reply to this message, or to the comment if this is a comment reply or mention.
+    pub fn reply(&self, body: &str) -> Result<()> {
+        self.client.ensure_user()?;
+
+        let text = self
+            .client
+            .post(
+                &format!("{}api/comment", URL),
+                &[
+                    ("api_type", "json"),
+                    ("thing_id", &self.name.to_string()),
+                    ("text", body),
+                ],
+            )?
+            .text()?;
+
+        check_api_errors(&text)
+    }
+
+    /// Mark this message as read.
+    pub fn mark_read(&self) -> Result<()> {
+        self.action("read_message")
+    }
+
+    /// Mark this message as unread.
+    pub fn mark_unread(&self) -> Result<()> {
+        self.action("unread_message")
+    }
+
+    /// Collapse this message in the inbox.
+    pub fn collapse(&self) -> Result<()> {
+        self.action("collapse_message")
+    }
+
+    /// Expand this message in the inbox, if it has been collapsed.
+    pub fn uncollapse(&self) -> Result<()> {
+        self.action("uncollapse_message")
+    }
+
+    /// Delete this message from the inbox of the logged in user. Only applies to private messages.
+    pub fn delete(&self) -> Result<()> {
+        self.action("del_msg")
+    }
+
+    fn action(&self, path: &str) -> Result<()> {
+        self.client.ensure_user()?;
+
+        self.client
+            .post(&format!("{}api/{}", URL, path), &[("id", &self.name)])?;
+
+        Ok(())
+    }
+}
+
+impl<T: Authenticator> FromListing<T> for Message<T> {
+    type Raw = RawKind<RawMessageData>;
+
+    fn from_raw(raw: Self::Raw, client: &AuthenticatedClient<T>) -> Result<Self> {
+        let raw = raw.data;
+
+        Ok(Self {
+            client: client.clone(),
+            id: raw.id,
+            name: raw.name,
+            author: raw.author,
+            dest: raw.dest,
+            subject: raw.subject,
+            body: raw.body,
+            created_utc: raw.created_utc,
+            new: raw.new,
+            was_comment: raw.was_comment,
+            context: raw.context,
+            parent_id: raw.parent_id,
+            first_message_name: raw.first_message_name,
+            subreddit: raw.subreddit,
+            distinguished: raw.distinguished,
+        })
+    }
+}
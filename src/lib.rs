@@ -49,6 +49,16 @@
 )]
 #![deny(unsafe_code)]
 pub mod auth;
+pub mod flair;
+pub mod inbox;
+pub mod media;
+pub mod moderation;
+pub mod modmail;
+pub mod multireddit;
 pub mod reddit;
+pub mod rules;
+pub mod settings;
+pub mod stream;
 mod tests;
 pub mod things;
+pub mod wiki;
@@ -0,0 +1,159 @@
+//! Images, videos and galleries attached to posts. See [`crate::things::Post::media_urls`].
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::things::raw::media::{RawGalleryData, RawMediaMetadata, RawMediaSource};
+
+/// Preview images Reddit generated for a post, e.g. of a linked image or article.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Preview {
+    pub images: Vec<PreviewImage>,
+    /// Whether Reddit shows the preview in place of the link.
+    #[serde(default)]
+    pub enabled: bool,
+}
+
+/// One preview image, in several resolutions.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PreviewImage {
+    pub id: String,
+    /// The original image.
+    pub source: Image,
+    /// Smaller versions of the image, smallest first.
+    #[serde(default)]
+    pub resolutions: Vec<Image>,
+    /// Other formats of the image, e.g. `gif` and `mp4` for animated images, or `nsfw` and `obfuscated` for blurred versions.
+    #[serde(default)]
+    pub variants: HashMap<String, PreviewVariant>,
+}
+
+/// Another format of a [`PreviewImage`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PreviewVariant {
+    pub source: Image,
+    #[serde(default)]
+    pub resolutions: Vec<Image>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Image {
+    pub url: String,
+    pub width: u32,
+    pub height: u32,
+}
+
+/// Media embedded in a post, either a video hosted by Reddit or an embed from another site such as YouTube.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Media {
+    /// The site the embed is from, e.g. `youtube.com`. None for Reddit videos.
+    #[serde(rename = "type")]
+    pub provider: Option<String>,
+    pub reddit_video: Option<RedditVideo>,
+    pub oembed: Option<Oembed>,
+}
+
+/// A video hosted by Reddit, on v.redd.it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RedditVideo {
+    /// A MP4 of the video in the highest quality, without audio.
+    pub fallback_url: String,
+    /// A MPEG-DASH playlist, including audio.
+    pub dash_url: String,
+    /// A HLS playlist, including audio.
+    pub hls_url: String,
+    pub width: u32,
+    pub height: u32,
+    /// Seconds.
+    pub duration: u32,
+    /// Converted from a gif, and thus without audio.
+    #[serde(default)]
+    pub is_gif: bool,
+    pub bitrate_kbps: Option<u32>,
+}
+
+/// An embed from another site, see [the oEmbed specification](https://oembed.com/).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Oembed {
+    /// E.g. `video` or `rich`.
+    #[serde(rename = "type")]
+    pub kind: Option<String>,
+    pub provider_name: Option<String>,
+    pub provider_url: Option<String>,
+    pub title: Option<String>,
+    pub author_name: Option<String>,
+    pub author_url: Option<String>,
+    /// HTML to embed the media, e.g. an iframe.
+    pub html: Option<String>,
+    pub width: Option<u32>,
+    pub height: Option<u32>,
+    pub thumbnail_url: Option<String>,
+    pub thumbnail_width: Option<u32>,
+    pub thumbnail_height: Option<u32>,
+}
+
+/// One image of a gallery post.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GalleryItem {
+    pub media_id: String,
+    pub caption: Option<String>,
+    /// A link the uploader attached to the image.
+    pub outbound_url: Option<String>,
+    /// E.g. `image/jpg` or `image/gif`.
+    pub mime_type: Option<String>,
+    /// The original image. None if Reddit failed to process the upload.
+    pub source: Option<Image>,
+    /// For animated images, a MP4 version of [`Self::source`].
+    pub mp4: Option<String>,
+    /// Smaller versions of the image, smallest first.
+    pub resolutions: Vec<Image>,
+}
+
+// Gallery posts list the images in gallery_data, in order, but describe them in media_metadata.
+pub(crate) fn gallery(
+    data: Option<RawGalleryData>,
+    metadata: Option<HashMap<String, RawMediaMetadata>>,
+) -> Vec<GalleryItem> {
+    let (data, mut metadata) = match (data, metadata) {
+        (Some(data), Some(metadata)) => (data, metadata),
+        _ => return Vec::new(),
+    };
+
+    data.items
+        .into_iter()
+        .map(|item| {
+            let metadata = metadata.remove(&item.media_id);
+
+            GalleryItem {
+                mime_type: metadata.as_ref().and_then(|metadata| metadata.m.clone()),
+                source: metadata
+                    .as_ref()
+                    .and_then(|metadata| metadata.s.as_ref())
+                    .and_then(image),
+                mp4: metadata
+                    .as_ref()
+                    .and_then(|metadata| metadata.s.as_ref())
+                    .and_then(|source| source.mp4.clone()),
+                resolutions: metadata
+                    .map(|metadata| metadata.p.iter().filter_map(image).collect())
+                    .unwrap_or_default(),
+                media_id: item.media_id,
+                caption: item.caption,
+                outbound_url: item.outbound_url,
+            }
+        })
+        .collect()
+}
+
+// Images have u, animated images have gif instead.
+fn image(source: &RawMediaSource) -> Option<Image> {
+    source
+        .u
+        .as_deref()
+        .or(source.gif.as_deref())
+        .map(|url| Image {
+            url: String::from(url),
+            width: source.x,
+            height: source.y,
+        })
+}
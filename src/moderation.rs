@@ -0,0 +1,788 @@
+//! Moderation of subreddits, posts and comments. Requires the logged in user to moderate the subreddit.
+use std::time::SystemTime;
+
+use serde::Deserialize;
+
+use crate::{
+    auth::{AuthenticatedClient, Authenticator},
+    reddit::{check_api_errors, Error, Result, URL},
+    things::{
+        raw, raw::generic_kind::RawKind, CommentSort, Feed, FromListing, Fullname, PostFeed, Thing,
+    },
+};
+
+/// Moderation queues, the moderation log and the user lists of a subreddit. Use [`crate::things::Subreddit::mod_`] to create one.
+/// ```no_run
+/// # fn main() -> snew::reddit::Result<()> {
+/// # use snew::{reddit::Reddit, auth::{ScriptAuthenticator, Credentials}};
+/// # let reddit = Reddit::new(
+/// #    ScriptAuthenticator::new(Credentials::new("client_id", "client_secret", "username", "password")),
+/// #    "<Operating system>:snew:v0.1.0 (by u/<reddit username>)"
+/// #    ).unwrap();
+/// use snew::things::Thing;
+///
+/// let moderation = reddit.subreddit("mysubreddit").mod_();
+///
+/// for thing in moderation.reports() {
+///     match thing? {
+///         Thing::Post(post) => println!("Reported post: {}", post.data.title),
+///         Thing::Comment(comment) => println!("Reported comment: {}", comment.data.body),
+///     }
+/// }
+///
+/// for action in moderation.log().action("removelink").moderator("AutoModerator").take(10) {
+///     let action = action?;
+///     println!("{} removed {:?}", action.moderator, action.target_title);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct SubredditModeration<T: Authenticator> {
+    /// The URL of the subreddit.
+    pub url: String,
+    client: AuthenticatedClient<T>,
+}
+
+impl<T: Authenticator> SubredditModeration<T> {
+    /// Create a instance of subreddit moderation
+    /// Use [`crate::things::Subreddit::mod_()`] instead.
+    pub fn create(url: &str, client: &AuthenticatedClient<T>) -> Self {
+        Self {
+            url: String::from(url),
+            client: client.clone(),
+        }
+    }
+
+    /// Posts and comments that are reported, or removed by the spam filter, and waiting for review.
+    pub fn modqueue(&self) -> Feed<T, Thing<T>> {
+        self.listing("modqueue")
+    }
+
+    /// Posts and comments that are reported.
+    pub fn reports(&self) -> Feed<T, Thing<T>> {
+        self.listing("reports")
+    }
+
+    /// Posts and comments that are removed as spam, either by moderators or the spam filter.
+    pub fn spam(&self) -> Feed<T, Thing<T>> {
+        self.listing("spam")
+    }
+
+    /// Posts and comments that have been edited, most recently edited first.
+    pub fn edited(&self) -> Feed<T, Thing<T>> {
+        self.listing("edited")
+    }
+
+    /// Posts that no moderator has approved or removed yet.
+    pub fn unmoderated(&self) -> PostFeed<T> {
+        self.listing("unmoderated")
+    }
+
+    /// The moderation log, most recent action first. See [`Feed::action`] and [`Feed::moderator`] for filtering the log.
+    pub fn log(&self) -> Feed<T, ModAction> {
+        self.listing("log")
+    }
+
+    /// Users banned from this subreddit.
+    pub fn banned(&self) -> Feed<T, Relationship> {
+        self.listing("banned")
+    }
+
+    /// Ban a user from this subreddit. See [`BanOptions`].
+    /// ```no_run
+    /// # fn main() -> snew::reddit::Result<()> {
+    /// # use snew::{reddit::Reddit, auth::{ScriptAuthenticator, Credentials}};
+    /// # let reddit = Reddit::new(
+    /// #    ScriptAuthenticator::new(Credentials::new("client_id", "client_secret", "username", "password")),
+    /// #    "<Operating system>:snew:v0.1.0 (by u/<reddit username>)"
+    /// #    ).unwrap();
+    /// use snew::moderation::BanOptions;
+    ///
+    /// reddit.subreddit("mysubreddit").mod_().ban(
+    ///     "spammer",
+    ///     &BanOptions {
+    ///         duration: Some(7),
+    ///         reason: Some(String::from("Spam")),
+    ///         ..Default::default()
+    ///     },
+    /// )?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn ban(&self, username: &str, options: &BanOptions) -> Result<()> {
+        let form = ban_form(username, options);
+        let form: Vec<(&str, &str)> = form
+            .iter()
+            .map(|(key, value)| (*key, value.as_str()))
+            .collect();
+
+        self.friend(&form)
+    }
+
+    /// Unban a user from this subreddit.
+    pub fn unban(&self, username: &str) -> Result<()> {
+        self.unfriend(username, "banned")
+    }
+
+    /// Users muted in this subreddit, who cannot send modmail.
+    pub fn muted(&self) -> Feed<T, Relationship> {
+        self.listing("muted")
+    }
+
+    /// Mute a user, so they cannot send modmail to this subreddit.
+    pub fn mute(&self, username: &str) -> Result<()> {
+        self.friend(&[("api_type", "json"), ("name", username), ("type", "muted")])
+    }
+
+    /// Unmute a user.
+    pub fn unmute(&self, username: &str) -> Result<()> {
+        self.unfriend(username, "muted")
+    }
+
+    /// Approved users of this subreddit.
+    pub fn contributors(&self) -> Feed<T, Relationship> {
+        self.listing("contributors")
+    }
+
+    /// Add a user to the approved users of this subreddit.
+    pub fn add_contributor(&self, username: &str) -> Result<()> {
+        self.friend(&[
+            ("api_type", "json"),
+            ("name", username),
+            ("type", "contributor"),
+        ])
+    }
+
+    /// Remove a user from the approved users of this subreddit.
+    pub fn remove_contributor(&self, username: &str) -> Result<()> {
+        self.unfriend(username, "contributor")
+    }
+
+    /// Moderators of this subreddit, with their permissions.
+    pub fn moderators(&self) -> Feed<T, Relationship> {
+        self.listing("moderators")
+    }
+
+    /// Invite a user to moderate this subreddit, with the given permissions.
+    /// The user becomes a moderator after accepting the invite, see [`crate::things::Subreddit::accept_moderator_invite`].
+    /// Errors with [`Error::UnknownValueError`] if a permission is [`ModPermission::Other`].
+    pub fn invite_moderator(&self, username: &str, permissions: &[ModPermission]) -> Result<()> {
+        self.friend(&[
+            ("api_type", "json"),
+            ("name", username),
+            ("type", "moderator_invite"),
+            ("permissions", &self::permissions(permissions)?),
+        ])
+    }
+
+    /// Take back an invite to moderate this subreddit.
+    pub fn remove_moderator_invite(&self, username: &str) -> Result<()> {
+        self.unfriend(username, "moderator_invite")
+    }
+
+    /// Change the permissions of a moderator of this subreddit.
+    /// Errors with [`Error::UnknownValueError`] if a permission is [`ModPermission::Other`].
+    pub fn set_moderator_permissions(
+        &self,
+        username: &str,
+        permissions: &[ModPermission],
+    ) -> Result<()> {
+        post_checked(
+            &self.client,
+            &format!("{}/api/setpermissions", self.url),
+            &[
+                ("api_type", "json"),
+                ("name", username),
+                ("type", "moderator"),
+                ("permissions", &self::permissions(permissions)?),
+            ],
+        )
+    }
+
+    /// Remove a moderator of this subreddit.
+    pub fn remove_moderator(&self, username: &str) -> Result<()> {
+        self.unfriend(username, "moderator")
+    }
+
+    /// Users banned from editing the wiki of this subreddit.
+    pub fn wikibanned(&self) -> Feed<T, Relationship> {
+        self.listing("wikibanned")
+    }
+
+    /// Ban a user from editing the wiki of this subreddit.
+    pub fn wikiban(&self, username: &str) -> Result<()> {
+        self.friend(&[
+            ("api_type", "json"),
+            ("name", username),
+            ("type", "wikibanned"),
+        ])
+    }
+
+    /// Allow a user to edit the wiki of this subreddit again.
+    pub fn unwikiban(&self, username: &str) -> Result<()> {
+        self.unfriend(username, "wikibanned")
+    }
+
+    fn friend(&self, form: &[(&str, &str)]) -> Result<()> {
+        post_checked(&self.client, &format!("{}/api/friend", self.url), form)
+    }
+
+    fn unfriend(&self, username: &str, kind: &str) -> Result<()> {
+        post_checked(
+            &self.client,
+            &format!("{}/api/unfriend", self.url),
+            &[("api_type", "json"), ("name", username), ("type", kind)],
+        )
+    }
+
+    fn listing<I>(&self, path: &str) -> Feed<T, I> {
+        Feed::new(format!("{}/about/{}", self.url, path), &self.client)
+    }
+}
+
+/// A user in one of the user lists of a subreddit, such as [`SubredditModeration::banned`].
+#[derive(Debug, Clone, Deserialize)]
+pub struct Relationship {
+    /// The username.
+    pub name: String,
+    /// The fullname of the user, e.g. `t2_1a2b3c`.
+    pub id: Fullname,
+    /// When the user was added to the list.
+    #[serde(with = "raw::timestamp")]
+    pub date: SystemTime,
+    /// The moderator note, for banned users.
+    pub note: Option<String>,
+    /// Days until the ban expires, for banned users. None if the ban is permanent.
+    pub days_left: Option<i64>,
+    /// The permissions of moderators. Empty for other lists.
+    #[serde(default)]
+    pub mod_permissions: Vec<ModPermission>,
+}
+
+impl<T: Authenticator> FromListing<T> for Relationship {
+    // User lists do not wrap their children in a kind.
+    type Raw = Relationship;
+
+    fn from_raw(raw: Self::Raw, _: &AuthenticatedClient<T>) -> Result<Self> {
+        Ok(raw)
+    }
+}
+
+/// How to ban a user. See [`SubredditModeration::ban`].
+/// All fields are optional, the default is a permanent ban without any reason or message.
+#[derive(Debug, Clone, Default)]
+pub struct BanOptions {
+    /// The length of the ban in days. None for a permanent ban.
+    pub duration: Option<u32>,
+    /// The reason for the ban, only visible to moderators. At most 100 characters.
+    pub reason: Option<String>,
+    /// A note about the ban, only visible to moderators. At most 300 characters.
+    pub note: Option<String>,
+    /// A message sent to the banned user.
+    pub message: Option<String>,
+}
+
+/// What a moderator is allowed to do.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ModPermission {
+    /// Full permissions, including adding other moderators.
+    All,
+    /// Manage approved and banned users.
+    Access,
+    ChatConfig,
+    ChatOperator,
+    /// Manage settings, rules and styles.
+    Config,
+    Flair,
+    /// Read and reply to modmail.
+    Mail,
+    /// Approve and remove posts and comments.
+    Posts,
+    Wiki,
+    /// A permission not known to snew.
+    #[serde(other)]
+    Other,
+}
+
+impl ModPermission {
+    fn as_str(&self) -> Option<&'static str> {
+        match self {
+            Self::All => Some("all"),
+            Self::Access => Some("access"),
+            Self::ChatConfig => Some("chat_config"),
+            Self::ChatOperator => Some("chat_operator"),
+            Self::Config => Some("config"),
+            Self::Flair => Some("flair"),
+            Self::Mail => Some("mail"),
+            Self::Posts => Some("posts"),
+            Self::Wiki => Some("wiki"),
+            Self::Other => None,
+        }
+    }
+}
+
+/// An entry in the moderation log. See [`SubredditModeration::log`].
+#[derive(Debug, Clone, Deserialize)]
+pub struct ModAction {
+    pub id: String,
+    /// The kind of action, e.g. `removelink` or `banuser`.
+    pub action: String,
+    /// The name of the moderator that performed the action.
+    #[serde(rename = "mod")]
+    pub moderator: String,
+    #[serde(with = "raw::timestamp")]
+    pub created_utc: SystemTime,
+    pub subreddit: String,
+    /// Details about the action, e.g. the duration of a ban.
+    pub details: Option<String>,
+    pub description: Option<String>,
+    /// The author of the post or comment, or the user, the action was performed on.
+    pub target_author: Option<String>,
+    /// The fullname of the post or comment the action was performed on.
+    pub target_fullname: Option<Fullname>,
+    pub target_permalink: Option<String>,
+    pub target_title: Option<String>,
+    pub target_body: Option<String>,
+}
+
+impl<T: Authenticator> FromListing<T> for ModAction {
+    type Raw = RawKind<ModAction>;
+
+    fn from_raw(raw: Self::Raw, _: &AuthenticatedClient<T>) -> Result<Self> {
+        Ok(raw.data)
+    }
+}
+
+impl<T: Authenticator> Feed<T, ModAction> {
+    /// Only include actions of this kind, e.g. `removelink` or `banuser`.
+    pub fn action(self, action: &str) -> Self {
+        self.param("type", action)
+    }
+
+    /// Only include actions performed by this moderator.
+    pub fn moderator(self, moderator: &str) -> Self {
+        self.param("mod", moderator)
+    }
+}
+
+/// Moderation actions on a post. Use [`crate::things::Post::mod_`] to create one.
+/// ```no_run
+/// # fn main() -> snew::reddit::Result<()> {
+/// # use snew::{reddit::Reddit, auth::{ScriptAuthenticator, Credentials}};
+/// # let reddit = Reddit::new(
+/// #    ScriptAuthenticator::new(Credentials::new("client_id", "client_secret", "username", "password")),
+/// #    "<Operating system>:snew:v0.1.0 (by u/<reddit username>)"
+/// #    ).unwrap();
+/// for post in reddit.subreddit("mysubreddit").new().take(10) {
+///     let post = post?;
+///
+///     if post.data.title.contains("spam") {
+///         post.mod_().remove(true)?;
+///     }
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct PostModeration<T: Authenticator> {
+    /// The fullname of the post.
+    pub fullname: Fullname,
+    client: AuthenticatedClient<T>,
+}
+
+impl<T: Authenticator> PostModeration<T> {
+    /// Create a instance of post moderation
+    /// Use [`crate::things::Post::mod_()`] instead.
+    pub fn create(fullname: Fullname, client: &AuthenticatedClient<T>) -> Self {
+        Self {
+            fullname,
+            client: client.clone(),
+        }
+    }
+
+    /// Approve this post, restoring it if it was removed.
+    pub fn approve(&self) -> Result<()> {
+        action(
+            &self.client,
+            "approve",
+            &[("id", &self.fullname.to_string())],
+        )
+    }
+
+    /// Remove this post. If `spam` is true, it also trains the spam filter.
+    pub fn remove(&self, spam: bool) -> Result<()> {
+        remove(&self.client, &self.fullname, spam)
+    }
+
+    /// Attach a removal reason to this post, after it has been removed.
+    /// `reason_id` is the id of one of the removal reasons of the subreddit, if any.
+    pub fn add_removal_reason(
+        &self,
+        reason_id: Option<&str>,
+        mod_note: Option<&str>,
+    ) -> Result<()> {
+        add_removal_reason(&self.client, &self.fullname, reason_id, mod_note)
+    }
+
+    /// Mark this post as posted by a moderator.
+    pub fn distinguish(&self) -> Result<()> {
+        distinguish(&self.client, &self.fullname, "yes", false)
+    }
+
+    /// Remove the moderator distinction from this post.
+    pub fn undistinguish(&self) -> Result<()> {
+        distinguish(&self.client, &self.fullname, "no", false)
+    }
+
+    /// Sticky this post at the top of the subreddit.
+    /// Subreddits have two sticky slots. If `bottom` is true, the post replaces the bottom one, otherwise the top one.
+    pub fn sticky(&self, bottom: bool) -> Result<()> {
+        let fullname = self.fullname.to_string();
+        let mut form = vec![
+            ("api_type", "json"),
+            ("id", fullname.as_str()),
+            ("state", "true"),
+        ];
+
+        if !bottom {
+            form.push(("num", "1"));
+        }
+
+        action(&self.client, "set_subreddit_sticky", &form)
+    }
+
+    /// Remove this post from the sticky slots.
+    pub fn unsticky(&self) -> Result<()> {
+        action(
+            &self.client,
+            "set_subreddit_sticky",
+            &[
+                ("api_type", "json"),
+                ("id", &self.fullname.to_string()),
+                ("state", "false"),
+            ],
+        )
+    }
+
+    /// Lock this post, so that no new comments can be made.
+    pub fn lock(&self) -> Result<()> {
+        action(&self.client, "lock", &[("id", &self.fullname.to_string())])
+    }
+
+    /// Unlock this post.
+    pub fn unlock(&self) -> Result<()> {
+        action(
+            &self.client,
+            "unlock",
+            &[("id", &self.fullname.to_string())],
+        )
+    }
+
+    /// Mark this post as NSFW.
+    pub fn nsfw(&self) -> Result<()> {
+        action(
+            &self.client,
+            "marknsfw",
+            &[("id", &self.fullname.to_string())],
+        )
+    }
+
+    /// Remove the NSFW mark from this post.
+    pub fn sfw(&self) -> Result<()> {
+        action(
+            &self.client,
+            "unmarknsfw",
+            &[("id", &self.fullname.to_string())],
+        )
+    }
+
+    /// Mark this post as a spoiler.
+    pub fn spoiler(&self) -> Result<()> {
+        action(
+            &self.client,
+            "spoiler",
+            &[("id", &self.fullname.to_string())],
+        )
+    }
+
+    /// Remove the spoiler mark from this post.
+    pub fn unspoiler(&self) -> Result<()> {
+        action(
+            &self.client,
+            "unspoiler",
+            &[("id", &self.fullname.to_string())],
+        )
+    }
+
+    /// Enable or disable contest mode, where comments are randomly sorted and their scores are hidden.
+    pub fn contest_mode(&self, enabled: bool) -> Result<()> {
+        action(
+            &self.client,
+            "set_contest_mode",
+            &[
+                ("api_type", "json"),
+                ("id", &self.fullname.to_string()),
+                ("state", if enabled { "true" } else { "false" }),
+            ],
+        )
+    }
+
+    /// Set the default sort order of the comments on this post, or clear it with None.
+    /// Errors with [`Error::UnknownValueError`] if the sort is [`CommentSort::Other`].
+    pub fn suggested_sort(&self, sort: Option<CommentSort>) -> Result<()> {
+        let sort = match sort {
+            Some(sort) => sort.as_str().ok_or_else(|| {
+                Error::UnknownValueError(String::from("the comment sort is unknown"))
+            })?,
+            None => "blank",
+        };
+
+        action(
+            &self.client,
+            "set_suggested_sort",
+            &[
+                ("api_type", "json"),
+                ("id", &self.fullname.to_string()),
+                ("sort", sort),
+            ],
+        )
+    }
+
+    /// Ignore future reports on this post.
+    pub fn ignore_reports(&self) -> Result<()> {
+        action(
+            &self.client,
+            "ignore_reports",
+            &[("id", &self.fullname.to_string())],
+        )
+    }
+
+    /// Stop ignoring reports on this post.
+    pub fn unignore_reports(&self) -> Result<()> {
+        action(
+            &self.client,
+            "unignore_reports",
+            &[("id", &self.fullname.to_string())],
+        )
+    }
+}
+
+/// Moderation actions on a comment. Use [`crate::things::Comment::mod_`] to create one.
+#[derive(Debug)]
+pub struct CommentModeration<T: Authenticator> {
+    /// The fullname of the comment.
+    pub fullname: Fullname,
+    client: AuthenticatedClient<T>,
+}
+
+impl<T: Authenticator> CommentModeration<T> {
+    /// Create a instance of comment moderation
+    /// Use [`crate::things::Comment::mod_()`] instead.
+    pub fn create(fullname: Fullname, client: &AuthenticatedClient<T>) -> Self {
+        Self {
+            fullname,
+            client: client.clone(),
+        }
+    }
+
+    /// Approve this comment, restoring it if it was removed.
+    pub fn approve(&self) -> Result<()> {
+        action(
+            &self.client,
+            "approve",
+            &[("id", &self.fullname.to_string())],
+        )
+    }
+
+    /// Remove this comment. If `spam` is true, it also trains the spam filter.
+    pub fn remove(&self, spam: bool) -> Result<()> {
+        remove(&self.client, &self.fullname, spam)
+    }
+
+    /// Attach a removal reason to this comment, after it has been removed.
+    /// `reason_id` is the id of one of the removal reasons of the subreddit, if any.
+    pub fn add_removal_reason(
+        &self,
+        reason_id: Option<&str>,
+        mod_note: Option<&str>,
+    ) -> Result<()> {
+        add_removal_reason(&self.client, &self.fullname, reason_id, mod_note)
+    }
+
+    /// Mark this comment as made by a moderator.
+    /// If `sticky` is true, the comment is also pinned to the top of the post. Only top level comments can be stickied.
+    pub fn distinguish(&self, sticky: bool) -> Result<()> {
+        distinguish(&self.client, &self.fullname, "yes", sticky)
+    }
+
+    /// Remove the moderator distinction from this comment, and unsticky it.
+    pub fn undistinguish(&self) -> Result<()> {
+        distinguish(&self.client, &self.fullname, "no", false)
+    }
+
+    /// Lock this comment, so that no replies can be made.
+    pub fn lock(&self) -> Result<()> {
+        action(&self.client, "lock", &[("id", &self.fullname.to_string())])
+    }
+
+    /// Unlock this comment.
+    pub fn unlock(&self) -> Result<()> {
+        action(
+            &self.client,
+            "unlock",
+            &[("id", &self.fullname.to_string())],
+        )
+    }
+
+    /// Ignore future reports on this comment.
+    pub fn ignore_reports(&self) -> Result<()> {
+        action(
+            &self.client,
+            "ignore_reports",
+            &[("id", &self.fullname.to_string())],
+        )
+    }
+
+    /// Stop ignoring reports on this comment.
+    pub fn unignore_reports(&self) -> Result<()> {
+        action(
+            &self.client,
+            "unignore_reports",
+            &[("id", &self.fullname.to_string())],
+        )
+    }
+}
+
+// Post to api/{path}.
+fn action<T: Authenticator>(
+    client: &AuthenticatedClient<T>,
+    path: &str,
+    form: &[(&str, &str)],
+) -> Result<()> {
+    post_checked(client, &format!("{}api/{}", URL, path), form)
+}
+
+// Post to url, reporting any errors Reddit includes in the response.
+fn post_checked<T: Authenticator>(
+    client: &AuthenticatedClient<T>,
+    url: &str,
+    form: &[(&str, &str)],
+) -> Result<()> {
+    client.ensure_user()?;
+
+    let text = client.post(url, form)?.text()?;
+
+    check_api_errors(&text)
+}
+
+// Formats permissions the way Reddit expects them, e.g. `-all,+posts,+wiki`.
+// Errors if a permission is unknown, rather than leaving it out.
+pub(crate) fn permissions(permissions: &[ModPermission]) -> Result<String> {
+    let mut formatted = String::from("-all");
+
+    for permission in permissions {
+        let permission = permission.as_str().ok_or_else(|| {
+            Error::UnknownValueError(String::from("a moderator permission is unknown"))
+        })?;
+        formatted.push_str(",+");
+        formatted.push_str(permission);
+    }
+
+    if permissions.contains(&ModPermission::All) {
+        return Ok(String::from("+all"));
+    }
+
+    Ok(formatted)
+}
+
+fn remove<T: Authenticator>(
+    client: &AuthenticatedClient<T>,
+    fullname: &Fullname,
+    spam: bool,
+) -> Result<()> {
+    action(
+        client,
+        "remove",
+        &[
+            ("id", &fullname.to_string()),
+            ("spam", if spam { "true" } else { "false" }),
+        ],
+    )
+}
+
+fn distinguish<T: Authenticator>(
+    client: &AuthenticatedClient<T>,
+    fullname: &Fullname,
+    how: &str,
+    sticky: bool,
+) -> Result<()> {
+    action(
+        client,
+        "distinguish",
+        &[
+            ("api_type", "json"),
+            ("id", &fullname.to_string()),
+            ("how", how),
+            ("sticky", if sticky { "true" } else { "false" }),
+        ],
+    )
+}
+
+fn add_removal_reason<T: Authenticator>(
+    client: &AuthenticatedClient<T>,
+    fullname: &Fullname,
+    reason_id: Option<&str>,
+    mod_note: Option<&str>,
+) -> Result<()> {
+    action(
+        client,
+        "v1/modactions/removal_reasons",
+        &[(
+            "json",
+            &removal_reason_json(&fullname.to_string(), reason_id, mod_note),
+        )],
+    )
+}
+
+// The form fields for banning a user with /api/friend.
+pub(crate) fn ban_form(username: &str, options: &BanOptions) -> Vec<(&'static str, String)> {
+    let mut form = vec![
+        ("api_type", String::from("json")),
+        ("name", String::from(username)),
+        ("type", String::from("banned")),
+    ];
+
+    let optional = [
+        (
+            "duration",
+            options.duration.map(|duration| duration.to_string()),
+        ),
+        ("ban_reason", options.reason.clone()),
+        ("note", options.note.clone()),
+        ("ban_message", options.message.clone()),
+    ];
+
+    form.extend(
+        optional
+            .iter()
+            .filter_map(|(key, value)| value.clone().map(|value| (*key, value))),
+    );
+
+    form
+}
+
+// The removal reasons endpoint takes its parameters as a JSON string in a form field.
+pub(crate) fn removal_reason_json(
+    fullname: &str,
+    reason_id: Option<&str>,
+    mod_note: Option<&str>,
+) -> String {
+    serde_json::json!({
+        "item_ids": [fullname],
+        "reason_id": reason_id,
+        "mod_note": mod_note,
+    })
+    .to_string()
+}
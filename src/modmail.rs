@@ -0,0 +1,468 @@
+//! Modmail, i.e. conversations between the moderators of a subreddit and users.
+use std::{collections::HashMap, time::SystemTime};
+
+use serde::Deserialize;
+
+use crate::{
+    auth::{AuthenticatedClient, Authenticator},
+    reddit::{Result, URL},
+    things::{
+        raw::{
+            self,
+            modmail::{RawConversation, RawConversationDetails, RawConversations},
+        },
+        Transpose,
+    },
+};
+
+/// A handle to the modmail of all subreddits the logged in user moderates.
+/// Use [`crate::reddit::Reddit::modmail`] to create one.
+/// ```no_run
+/// # fn main() -> snew::reddit::Result<()> {
+/// # use snew::{reddit::Reddit, auth::{ScriptAuthenticator, Credentials}};
+/// # let reddit = Reddit::new(
+/// #    ScriptAuthenticator::new(Credentials::new("client_id", "client_secret", "username", "password")),
+/// #    "<Operating system>:snew:v0.1.0 (by u/<reddit username>)"
+/// #    ).unwrap();
+/// use snew::modmail::ConversationState;
+///
+/// let modmail = reddit.modmail();
+///
+/// for conversation in modmail.conversations(ConversationState::New).subreddits(&["mysubreddit"]) {
+///     let conversation = conversation?;
+///     println!("{}", conversation.subject);
+///
+///     conversation.reply("Thanks, we will look into it.", false)?;
+///     conversation.archive()?;
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct Modmail<T: Authenticator> {
+    client: AuthenticatedClient<T>,
+}
+
+impl<T: Authenticator> Modmail<T> {
+    /// Create a instance of modmail
+    /// Use [`crate::reddit::Reddit::modmail()`] instead.
+    pub fn create(client: &AuthenticatedClient<T>) -> Self {
+        Self {
+            client: client.clone(),
+        }
+    }
+
+    /// Conversations in the given state, most recently updated first.
+    /// Includes all subreddits the logged in user moderates, see [`ConversationFeed::subreddits`] to filter them.
+    pub fn conversations(&self, state: ConversationState) -> ConversationFeed<T> {
+        ConversationFeed {
+            limit: 100,
+            state,
+            subreddits: None,
+            client: self.client.clone(),
+            cached: Vec::new(),
+            after: None,
+            exhausted: false,
+        }
+    }
+
+    /// Get a conversation by its id, with all its messages and mod actions.
+    pub fn conversation(&self, id: &str) -> Result<Conversation<T>> {
+        self.client.ensure_user()?;
+
+        let text = self
+            .client
+            .get(
+                &format!("{}api/mod/conversations/{}", URL, id),
+                Some(&[("markRead", "false")]),
+            )?
+            .text()?;
+
+        let details: RawConversationDetails = serde_json::from_str(&text)?;
+
+        Ok(Conversation::create(
+            details.conversation,
+            details.messages,
+            details.mod_actions,
+            &self.client,
+        ))
+    }
+}
+
+/// Which conversations to include. See [`Modmail::conversations`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConversationState {
+    All,
+    New,
+    InProgress,
+    /// Conversations between moderators only.
+    Mod,
+    /// Automatic notifications, such as reports.
+    Notifications,
+    Archived,
+    Highlighted,
+    JoinRequests,
+}
+
+impl ConversationState {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Self::All => "all",
+            Self::New => "new",
+            Self::InProgress => "inprogress",
+            Self::Mod => "mod",
+            Self::Notifications => "notifications",
+            Self::Archived => "archived",
+            Self::Highlighted => "highlighted",
+            Self::JoinRequests => "join_requests",
+        }
+    }
+}
+
+/// The state of a conversation. See [`Conversation::state`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(from = "i32")]
+pub enum ConversationStatus {
+    New,
+    InProgress,
+    Archived,
+    /// Some state not known to snew.
+    Other(i32),
+}
+
+impl From<i32> for ConversationStatus {
+    fn from(value: i32) -> Self {
+        match value {
+            0 => Self::New,
+            1 => Self::InProgress,
+            2 => Self::Archived,
+            _ => Self::Other(value),
+        }
+    }
+}
+
+/// A set of modmail conversations, meant to be iterated over. See [`Modmail::conversations`].
+#[derive(Debug)]
+pub struct ConversationFeed<T: Authenticator> {
+    /// The amount of conversations to request from the Reddit API at once. The max is 100, which is also the default.
+    pub limit: i32,
+    state: ConversationState,
+    subreddits: Option<String>,
+    client: AuthenticatedClient<T>,
+    cached: Vec<Conversation<T>>,
+    after: Option<String>,
+    exhausted: bool,
+}
+
+impl<T: Authenticator> ConversationFeed<T> {
+    /// Only include conversations of these subreddits.
+    pub fn subreddits(mut self, subreddits: &[&str]) -> Self {
+        self.subreddits = Some(subreddits.join(","));
+        self
+    }
+}
+
+impl<T: Authenticator> Iterator for ConversationFeed<T> {
+    type Item = Result<Conversation<T>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.cached.is_empty() && self.exhausted {
+            return None;
+        }
+
+        self.cached.pop().map(Ok).or_else_transpose(|| {
+            self.client.ensure_user()?;
+
+            let mut queries = vec![
+                ("limit", self.limit.to_string()),
+                ("state", String::from(self.state.as_str())),
+                ("sort", String::from("recent")),
+            ];
+
+            if let Some(subreddits) = &self.subreddits {
+                queries.push(("entity", subreddits.clone()));
+            }
+
+            if let Some(after) = &self.after {
+                queries.push(("after", after.clone()));
+            }
+
+            let text = self
+                .client
+                .get(&format!("{}api/mod/conversations", URL), Some(&queries))?
+                .text()?;
+
+            let RawConversations {
+                mut conversations,
+                conversation_ids,
+                mut messages,
+            } = serde_json::from_str(&text)?;
+
+            // Reddit does not say whether there are more conversations, so stop after the first page that is not full.
+            if conversation_ids.len() < self.limit as usize {
+                self.exhausted = true;
+            }
+            self.after = conversation_ids.last().cloned();
+
+            let client = &self.client;
+
+            for id in conversation_ids.iter().rev() {
+                if let Some(raw) = conversations.remove(id) {
+                    // The messages of all conversations on the page are in the same map.
+                    let own_messages = raw
+                        .obj_ids
+                        .iter()
+                        .filter_map(|obj| messages.remove_entry(&obj.id))
+                        .collect();
+
+                    self.cached.push(Conversation::create(
+                        raw,
+                        own_messages,
+                        HashMap::new(),
+                        client,
+                    ));
+                }
+            }
+
+            Ok(self.cached.pop())
+        })
+    }
+}
+
+/// A modmail conversation.
+#[derive(Debug)]
+pub struct Conversation<T: Authenticator> {
+    client: AuthenticatedClient<T>,
+    pub id: String,
+    pub subject: String,
+    pub state: ConversationStatus,
+    /// When the conversation was last updated.
+    pub last_updated: Option<SystemTime>,
+    pub is_highlighted: bool,
+    /// Whether this is a conversation between moderators only.
+    pub is_internal: bool,
+    /// Whether this conversation was started by Reddit, e.g. a notification.
+    pub is_auto: bool,
+    pub num_messages: i32,
+    /// The subreddit this conversation belongs to.
+    pub subreddit: String,
+    /// The non-moderator in this conversation, if any.
+    pub participant: Option<ModmailAuthor>,
+    pub authors: Vec<ModmailAuthor>,
+    /// The messages of this conversation, oldest first.
+    /// Conversations from [`Modmail::conversations`] only include the most recent messages, use [`Modmail::conversation`] to get all of them.
+    pub messages: Vec<ModmailMessage>,
+    /// Actions taken by moderators in this conversation, oldest first. Only included by [`Modmail::conversation`].
+    pub mod_actions: Vec<ModmailAction>,
+}
+
+impl<T: Authenticator> Clone for Conversation<T> {
+    fn clone(&self) -> Self {
+        Self {
+            client: self.client.clone(),
+            id: self.id.clone(),
+            subject: self.subject.clone(),
+            state: self.state,
+            last_updated: self.last_updated,
+            is_highlighted: self.is_highlighted,
+            is_internal: self.is_internal,
+            is_auto: self.is_auto,
+            num_messages: self.num_messages,
+            subreddit: self.subreddit.clone(),
+            participant: self.participant.clone(),
+            authors: self.authors.clone(),
+            messages: self.messages.clone(),
+            mod_actions: self.mod_actions.clone(),
+        }
+    }
+}
+
+impl<T: Authenticator> Conversation<T> {
+    fn create(
+        raw: RawConversation,
+        mut messages: HashMap<String, ModmailMessage>,
+        mut mod_actions: HashMap<String, ModmailAction>,
+        client: &AuthenticatedClient<T>,
+    ) -> Self {
+        let mut ordered_messages = Vec::new();
+        let mut ordered_actions = Vec::new();
+
+        for obj in &raw.obj_ids {
+            match obj.key.as_str() {
+                "messages" => ordered_messages.extend(messages.remove(&obj.id)),
+                "modActions" => ordered_actions.extend(mod_actions.remove(&obj.id)),
+                _ => {}
+            }
+        }
+
+        Self {
+            client: client.clone(),
+            id: raw.id,
+            subject: raw.subject,
+            state: raw.state,
+            last_updated: raw.last_updated,
+            is_highlighted: raw.is_highlighted,
+            is_internal: raw.is_internal,
+            is_auto: raw.is_auto,
+            num_messages: raw.num_messages,
+            subreddit: raw.owner.display_name,
+            participant: raw.participant.filter(|author| !author.name.is_empty()),
+            authors: raw.authors,
+            messages: ordered_messages,
+            mod_actions: ordered_actions,
+        }
+    }
+
+    /// Reply to this conversation. If `internal` is true, the reply is a private moderator note, invisible to the user.
+    pub fn reply(&self, body: &str, internal: bool) -> Result<()> {
+        self.client.ensure_user()?;
+
+        self.client.post(
+            &self.url(""),
+            &[
+                ("body", body),
+                ("isAuthorHidden", "false"),
+                ("isInternal", if internal { "true" } else { "false" }),
+            ],
+        )?;
+
+        Ok(())
+    }
+
+    /// Archive this conversation.
+    pub fn archive(&self) -> Result<()> {
+        self.action("/archive")
+    }
+
+    /// Move this conversation back out of the archive.
+    pub fn unarchive(&self) -> Result<()> {
+        self.action("/unarchive")
+    }
+
+    /// Highlight this conversation.
+    pub fn highlight(&self) -> Result<()> {
+        self.action("/highlight")
+    }
+
+    /// Remove the highlight from this conversation.
+    pub fn unhighlight(&self) -> Result<()> {
+        self.client.ensure_user()?;
+
+        self.client.delete(&self.url("/highlight"))?;
+
+        Ok(())
+    }
+
+    /// Mute the participant of this conversation, so they cannot send modmail to the subreddit.
+    pub fn mute_user(&self, duration: MuteDuration) -> Result<()> {
+        self.client.ensure_user()?;
+
+        self.client.post(
+            &self.url("/mute"),
+            &[("num_hours", duration.hours().to_string())],
+        )?;
+
+        Ok(())
+    }
+
+    /// Unmute the participant of this conversation.
+    pub fn unmute_user(&self) -> Result<()> {
+        self.action("/unmute")
+    }
+
+    /// Mark this conversation as read.
+    pub fn mark_read(&self) -> Result<()> {
+        self.mark("read")
+    }
+
+    /// Mark this conversation as unread.
+    pub fn mark_unread(&self) -> Result<()> {
+        self.mark("unread")
+    }
+
+    fn mark(&self, path: &str) -> Result<()> {
+        self.client.ensure_user()?;
+
+        self.client.post(
+            &format!("{}api/mod/conversations/{}", URL, path),
+            &[("conversationIds", &self.id)],
+        )?;
+
+        Ok(())
+    }
+
+    fn action(&self, path: &str) -> Result<()> {
+        self.client.ensure_user()?;
+
+        self.client.post(&self.url(path), &())?;
+
+        Ok(())
+    }
+
+    fn url(&self, path: &str) -> String {
+        format!("{}api/mod/conversations/{}{}", URL, self.id, path)
+    }
+}
+
+/// How long to mute a user. See [`Conversation::mute_user`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MuteDuration {
+    ThreeDays,
+    SevenDays,
+    TwentyEightDays,
+}
+
+impl MuteDuration {
+    fn hours(&self) -> i32 {
+        match self {
+            Self::ThreeDays => 72,
+            Self::SevenDays => 168,
+            Self::TwentyEightDays => 672,
+        }
+    }
+}
+
+/// A message in a modmail conversation.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModmailMessage {
+    pub id: String,
+    /// The text of this message, in markdown.
+    pub body_markdown: String,
+    /// The text of this message, as HTML.
+    pub body: String,
+    pub author: ModmailAuthor,
+    /// When this message was sent.
+    #[serde(deserialize_with = "raw::iso8601::deserialize")]
+    pub date: SystemTime,
+    /// Whether this is a private moderator note.
+    pub is_internal: bool,
+}
+
+/// An action taken by a moderator in a modmail conversation, e.g. archiving it.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModmailAction {
+    pub id: String,
+    /// The kind of action, as a number defined by Reddit. E.g. 0 for highlighting, 2 for archiving.
+    pub action_type_id: i32,
+    pub author: ModmailAuthor,
+    /// When this action was taken.
+    #[serde(deserialize_with = "raw::iso8601::deserialize")]
+    pub date: SystemTime,
+}
+
+/// An author of a message, or the participant of a modmail conversation.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ModmailAuthor {
+    pub name: String,
+    pub is_mod: bool,
+    pub is_admin: bool,
+    /// Whether this user started the conversation.
+    pub is_op: bool,
+    pub is_participant: bool,
+    /// Whether the author is hidden, i.e. the message was sent as the subreddit.
+    pub is_hidden: bool,
+    pub is_deleted: bool,
+}
@@ -0,0 +1,269 @@
+//! Multireddits, i.e. custom feeds combining several subreddits.
+use std::time::SystemTime;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    auth::{AuthenticatedClient, Authenticator},
+    reddit::{self, Result, URL},
+    things::{
+        raw::{generic_kind::RawKind, multireddit::RawMultireddit},
+        Feed, PostFeed,
+    },
+};
+
+/// A handle to interact with a multireddit, identified by its owner and name.
+/// See [`PostFeed`] for some gotchas when iterating over Posts.
+/// ```no_run
+/// # fn main() -> snew::reddit::Result<()> {
+/// # use snew::{reddit::Reddit, auth::{ScriptAuthenticator, Credentials}};
+/// # let reddit = Reddit::new(
+/// #    ScriptAuthenticator::new(Credentials::new("client_id", "client_secret", "username", "password")),
+/// #    "<Operating system>:snew:v0.1.0 (by u/<reddit username>)"
+/// #    ).unwrap();
+/// use snew::multireddit::MultiredditOptions;
+///
+/// let languages = reddit.create_multireddit(
+///     "languages",
+///     &MultiredditOptions {
+///         subreddits: vec![String::from("rust"), String::from("golang")],
+///         ..Default::default()
+///     },
+/// )?;
+/// languages.add_subreddit("haskell")?;
+///
+/// for post in languages.hot().take(10) {
+///     println!("{}", post?.data.title);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct Multireddit<T: Authenticator> {
+    /// The name of the redditor that owns this multireddit.
+    pub owner: String,
+    pub name: String,
+    pub url: String,
+    client: AuthenticatedClient<T>,
+}
+
+impl<T: Authenticator> Multireddit<T> {
+    /// Create a instance of a multireddit
+    /// Use [`crate::reddit::Reddit::multireddit()`] instead.
+    pub fn create(owner: &str, name: &str, client: &AuthenticatedClient<T>) -> Self {
+        Self {
+            owner: String::from(owner),
+            name: String::from(name),
+            url: format!("{}user/{}/m/{}", URL, owner, name),
+            client: client.clone(),
+        }
+    }
+
+    pub fn hot(&self) -> PostFeed<T> {
+        self.posts_sorted("hot")
+    }
+
+    // See Subreddit::new
+    #[allow(clippy::new_ret_no_self)]
+    pub fn new(&self) -> PostFeed<T> {
+        self.posts_sorted("new")
+    }
+
+    pub fn top(&self) -> PostFeed<T> {
+        self.posts_sorted("top")
+    }
+
+    pub fn rising(&self) -> PostFeed<T> {
+        self.posts_sorted("rising")
+    }
+
+    pub fn controversial(&self) -> PostFeed<T> {
+        self.posts_sorted("controversial")
+    }
+
+    /// Get metadata about this multireddit, such as the subreddits in it.
+    pub fn about(&self) -> Result<MultiredditInfo> {
+        let text = self.client.get(&self.api_url(""), None::<&()>)?.text()?;
+
+        let raw: RawKind<RawMultireddit> = serde_json::from_str(&text)?;
+
+        Ok(raw.data.into())
+    }
+
+    /// Add a subreddit to this multireddit.
+    pub fn add_subreddit(&self, subreddit: &str) -> Result<()> {
+        self.client.ensure_user()?;
+
+        let model = serde_json::json!({ "name": subreddit }).to_string();
+
+        self.client.put(
+            &self.api_url(&format!("/r/{}", subreddit)),
+            &[("model", model)],
+        )?;
+
+        Ok(())
+    }
+
+    /// Remove a subreddit from this multireddit.
+    pub fn remove_subreddit(&self, subreddit: &str) -> Result<()> {
+        self.client.ensure_user()?;
+
+        self.client
+            .delete(&self.api_url(&format!("/r/{}", subreddit)))?;
+
+        Ok(())
+    }
+
+    /// Rename this multireddit, changing both its name in the URL and its display name.
+    /// Returns a handle to the renamed multireddit, this handle no longer points to it.
+    pub fn rename(&self, name: &str, display_name: &str) -> Result<Multireddit<T>> {
+        let user = reddit::me(&self.client, URL)?.name;
+
+        self.client.post(
+            &format!("{}api/multi/rename", URL),
+            &[
+                ("from", self.path()),
+                ("to", format!("user/{}/m/{}", user, name)),
+                ("display_name", String::from(display_name)),
+            ],
+        )?;
+
+        Ok(Multireddit::create(&user, name, &self.client))
+    }
+
+    /// Copy this multireddit to the account of the logged in user, with the given name.
+    pub fn copy(&self, name: &str) -> Result<Multireddit<T>> {
+        let user = reddit::me(&self.client, URL)?.name;
+
+        self.client.post(
+            &format!("{}api/multi/copy", URL),
+            &[
+                ("from", self.path()),
+                ("to", format!("user/{}/m/{}", user, name)),
+                ("display_name", String::from(name)),
+            ],
+        )?;
+
+        Ok(Multireddit::create(&user, name, &self.client))
+    }
+
+    /// Delete this multireddit.
+    pub fn delete(&self) -> Result<()> {
+        self.client.ensure_user()?;
+
+        self.client.delete(&self.api_url(""))?;
+
+        Ok(())
+    }
+
+    fn path(&self) -> String {
+        format!("user/{}/m/{}", self.owner, self.name)
+    }
+
+    fn api_url(&self, path: &str) -> String {
+        format!("{}api/multi/{}{}", URL, self.path(), path)
+    }
+
+    fn posts_sorted(&self, path: &str) -> PostFeed<T> {
+        Feed::new(format!("{}/{}", self.url, path), &self.client)
+    }
+}
+
+// Lists of multireddits, e.g. of the logged in user.
+pub(crate) fn list<T: Authenticator>(
+    client: &AuthenticatedClient<T>,
+    url: &str,
+) -> Result<Vec<MultiredditInfo>> {
+    let text = client.get(url, None::<&()>)?.text()?;
+
+    let raw: Vec<RawKind<RawMultireddit>> = serde_json::from_str(&text)?;
+
+    Ok(raw.into_iter().map(|raw| raw.data.into()).collect())
+}
+
+/// Metadata about a multireddit. See [`Multireddit::about`].
+#[derive(Debug, Clone)]
+pub struct MultiredditInfo {
+    pub name: String,
+    pub display_name: String,
+    /// The name of the redditor that owns this multireddit.
+    pub owner: String,
+    /// The path of this multireddit, e.g. `/user/spez/m/languages`.
+    pub path: String,
+    pub description_md: String,
+    /// The names of the subreddits in this multireddit.
+    pub subreddits: Vec<String>,
+    pub visibility: Visibility,
+    pub over_18: bool,
+    pub num_subscribers: i32,
+    pub icon_url: Option<String>,
+    /// Whether the logged in user can edit this multireddit.
+    pub can_edit: bool,
+    pub created_utc: SystemTime,
+}
+
+impl From<RawMultireddit> for MultiredditInfo {
+    fn from(raw: RawMultireddit) -> Self {
+        Self {
+            name: raw.name,
+            display_name: raw.display_name,
+            owner: raw.owner,
+            path: raw.path,
+            description_md: raw.description_md,
+            subreddits: raw
+                .subreddits
+                .into_iter()
+                .map(|subreddit| subreddit.name)
+                .collect(),
+            visibility: raw.visibility,
+            over_18: raw.over_18,
+            num_subscribers: raw.num_subscribers,
+            icon_url: raw.icon_url,
+            can_edit: raw.can_edit,
+            created_utc: raw.created_utc,
+        }
+    }
+}
+
+/// Who can see a multireddit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Visibility {
+    /// Only the owner.
+    #[default]
+    Private,
+    /// Everyone.
+    Public,
+    /// Everyone with a link, but not listed on the profile of the owner.
+    Hidden,
+    /// A visibility not known to snew. Can not be used in [`MultiredditOptions`].
+    #[serde(other)]
+    Other,
+}
+
+/// The options for a new multireddit. See [`crate::reddit::Reddit::create_multireddit`].
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct MultiredditOptions {
+    /// Defaults to the name of the multireddit.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub display_name: Option<String>,
+    #[serde(rename = "description_md")]
+    pub description: String,
+    /// The names of the subreddits.
+    #[serde(serialize_with = "subreddit_names")]
+    pub subreddits: Vec<String>,
+    pub visibility: Visibility,
+}
+
+// Reddit expects the subreddits as objects, e.g. [{"name": "rust"}].
+fn subreddit_names<S: serde::Serializer>(
+    subreddits: &[String],
+    serializer: S,
+) -> std::result::Result<S::Ok, S::Error> {
+    #[derive(Serialize)]
+    struct Name<'b> {
+        name: &'b str,
+    }
+
+    serializer.collect_seq(subreddits.iter().map(|name| Name { name }))
+}
@@ -1,7 +1,14 @@
 //! Reddit API.
 use crate::auth::{AuthenticatedClient, Authenticator};
-use crate::things::*;
+use crate::inbox::Inbox;
+use crate::modmail::Modmail;
+use crate::multireddit::{self, Multireddit, MultiredditInfo, MultiredditOptions, Visibility};
+use crate::things::{
+    raw::{generic_kind::RawKind, listing::RawListing, RawTrophyList},
+    *,
+};
 
+use serde::Deserialize;
 use thiserror::Error;
 
 pub type Result<T> = std::result::Result<T, Error>;
@@ -31,12 +38,41 @@ pub const URL: &str = "https://oauth.reddit.com/";
 /// println!("{:?}", reddit.me().unwrap());
 /// ```
 /// See also [`Reddit::subreddit`].
-#[derive(Debug, Clone)]
+///
+/// Handles such as [`Subreddit`] and feeds such as [`PostFeed`] share the connection of the `Reddit` they were created from,
+/// but do not borrow it. They can be returned from functions and sent to other threads:
+/// ```no_run
+/// # use snew::{reddit::Reddit, auth::{ApplicationAuthenticator}};
+/// # let reddit = Reddit::new(
+/// #    ApplicationAuthenticator::new("client_id", "client_secret"),
+/// #    "<Operating system>:snew:v0.1.0 (by u/<reddit username>)"
+/// #    ).unwrap();
+/// let feed = reddit.subreddit("rust").new();
+///
+/// std::thread::spawn(move || {
+///     for post in feed.take(10) {
+///         println!("{}", post.unwrap().data.title);
+///     }
+/// })
+/// .join()
+/// .unwrap();
+/// ```
+#[derive(Debug)]
 pub struct Reddit<T: Authenticator> {
     client: AuthenticatedClient<T>,
     url: String,
 }
 
+// Derived Clone would require T: Clone, but the authenticator is shared.
+impl<T: Authenticator> Clone for Reddit<T> {
+    fn clone(&self) -> Self {
+        Self {
+            client: self.client.clone(),
+            url: self.url.clone(),
+        }
+    }
+}
+
 // The API calls.
 impl<T: Authenticator> Reddit<T> {
     /// Creates a new API connection, using the given authenticator.
@@ -49,24 +85,109 @@ impl<T: Authenticator> Reddit<T> {
         })
     }
 
+    pub(crate) fn client(&self) -> &AuthenticatedClient<T> {
+        &self.client
+    }
+
     /// Get information about the user, useful for debugging.
     pub fn me(&self) -> Result<Me> {
-        if self
+        me(&self.client, &self.url)
+    }
+
+    /// The karma of the logged in user, per subreddit.
+    pub fn karma_breakdown(&self) -> Result<Vec<SubredditKarma>> {
+        self.client.ensure_user()?;
+
+        let text = self
             .client
-            .authenticator
-            .lock()
-            .expect("Poisoned mutex, report bug at https://github.com/Zower/snew")
-            .is_user()
-        {
-            Ok(serde_json::from_str(
-                &self
-                    .client
-                    .get(&format!("{}{}", self.url, "api/v1/me"), None::<&()>)?
-                    .text()?,
-            )?)
-        } else {
-            Err(Error::NotLoggedInError)
-        }
+            .get(&format!("{}api/v1/me/karma", self.url), None::<&()>)?
+            .text()?;
+
+        let raw: RawKind<Vec<SubredditKarma>> = serde_json::from_str(&text)?;
+
+        Ok(raw.data)
+    }
+
+    /// The trophies of the logged in user.
+    pub fn trophies(&self) -> Result<Vec<Trophy>> {
+        self.client.ensure_user()?;
+
+        let text = self
+            .client
+            .get(&format!("{}api/v1/me/trophies", self.url), None::<&()>)?
+            .text()?;
+
+        let raw: RawKind<RawTrophyList> = serde_json::from_str(&text)?;
+
+        Ok(raw.data.trophies.into_iter().map(|raw| raw.data).collect())
+    }
+
+    /// The preferences of the logged in user.
+    pub fn preferences(&self) -> Result<Preferences> {
+        self.client.ensure_user()?;
+
+        Ok(serde_json::from_str(
+            &self
+                .client
+                .get(&format!("{}api/v1/me/prefs", self.url), None::<&()>)?
+                .text()?,
+        )?)
+    }
+
+    /// Update the preferences of the logged in user, returning the preferences as they are after the update.
+    /// Fields that are None are left unchanged.
+    /// ```no_run
+    /// # fn main() -> snew::reddit::Result<()> {
+    /// # use snew::{reddit::Reddit, auth::{ScriptAuthenticator, Credentials}};
+    /// # let reddit = Reddit::new(
+    /// #    ScriptAuthenticator::new(Credentials::new("client_id", "client_secret", "username", "password")),
+    /// #    "<Operating system>:snew:v0.1.0 (by u/<reddit username>)"
+    /// #    ).unwrap();
+    /// let mut preferences = reddit.preferences()?;
+    /// preferences.nightmode = Some(true);
+    ///
+    /// reddit.update_preferences(&preferences)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn update_preferences(&self, preferences: &Preferences) -> Result<Preferences> {
+        self.client.ensure_user()?;
+
+        Ok(serde_json::from_str(
+            &self
+                .client
+                .patch(&format!("{}api/v1/me/prefs", self.url), preferences)?
+                .text()?,
+        )?)
+    }
+
+    /// Create a handle into the inbox of the logged in user.
+    /// ```no_run
+    /// # fn main() -> snew::reddit::Result<()> {
+    /// # use snew::{reddit::Reddit, auth::{ScriptAuthenticator, Credentials}};
+    /// # let reddit = Reddit::new(
+    /// #    ScriptAuthenticator::new(Credentials::new("client_id", "client_secret", "username", "password")),
+    /// #    "<Operating system>:snew:v0.1.0 (by u/<reddit username>)"
+    /// #    ).unwrap();
+    /// let inbox = reddit.inbox();
+    ///
+    /// for message in inbox.unread() {
+    ///     let message = message?;
+    ///     println!("{}: {}", message.subject, message.body);
+    ///     message.mark_read()?;
+    /// }
+    ///
+    /// inbox.compose("spez", "Hello", "Nice site!")?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn inbox(&self) -> Inbox<T> {
+        Inbox::create(&self.client)
+    }
+
+    /// Create a handle into the modmail of the subreddits the logged in user moderates. See [`Modmail`].
+    pub fn modmail(&self) -> Modmail<T> {
+        Modmail::create(&self.client)
     }
 
     /// Create a handle into a specific subreddit.
@@ -91,7 +212,7 @@ impl<T: Authenticator> Reddit<T> {
     /// // You probably want to take() some elements, otherwise the Iterator will go as long as there are posts.
     /// for post in rust.hot().take(20) {
     ///     let post = post?;
-    ///     println!("{}", post.title);
+    ///     println!("{}", post.data.title);
     /// }
     /// // You can also set the request limit.
     /// // It changes how many posts are fetched from the Reddit API at once.
@@ -100,24 +221,216 @@ impl<T: Authenticator> Reddit<T> {
     ///
     /// for post in top.take(20) {
     ///     let post = post?;
-    ///     println!("{}", post.selftext);
+    ///     println!("{}", post.data.selftext);
     /// }
     /// # Ok(())
     /// # }
-
     pub fn subreddit(&self, name: &str) -> Subreddit<T> {
         Subreddit::create(name, &self.client)
     }
 
+    /// Create a handle into several subreddits at once, whose feeds combine the posts of all of them.
+    pub fn subreddits(&self, names: &[&str]) -> Subreddit<T> {
+        Subreddit::create(&names.join("+"), &self.client)
+    }
+
+    /// Posts from all subreddits. See [`Subreddit::exclude`] to leave out some subreddits.
+    pub fn all(&self) -> Subreddit<T> {
+        Subreddit::create("all", &self.client)
+    }
+
+    /// Popular posts, as shown on r/popular. See [`Subreddit::exclude`] to leave out some subreddits.
+    /// `geo_filter` limits the posts to a region, e.g. `GLOBAL`, a country code such as `US`, or a US state such as `US_CA`.
+    pub fn popular(&self, geo_filter: Option<&str>) -> Subreddit<T> {
+        let mut popular = Subreddit::create("popular", &self.client);
+        if let Some(geo_filter) = geo_filter {
+            popular.params.set("g", geo_filter);
+        }
+        popular
+    }
+
+    /// Create a handle into a specific redditor, i.e. a user.
+    /// ```no_run
+    /// # fn main() -> snew::reddit::Result<()> {
+    /// # use snew::{reddit::Reddit, auth::{ApplicationAuthenticator}};
+    /// # let reddit = Reddit::new(
+    /// #    ApplicationAuthenticator::new("client_id", "client_secret"),
+    /// #    "<Operating system>:snew:v0.1.0 (by u/<reddit username>)"
+    /// #    ).unwrap();
+    /// use snew::things::{TimeFilter, UserSort};
+    ///
+    /// let spez = reddit.redditor("spez");
+    /// println!("Karma: {}", spez.about()?.total_karma);
+    ///
+    /// for post in spez.submitted().sort(UserSort::Top).time(TimeFilter::All).take(5) {
+    ///     println!("{}", post?.data.title);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn redditor(&self, name: &str) -> Redditor<T> {
+        Redditor::create(name, &self.client)
+    }
+
+    /// Create a handle into a multireddit, by the name of its owner and its own name. See [`Multireddit`].
+    pub fn multireddit(&self, owner: &str, name: &str) -> Multireddit<T> {
+        Multireddit::create(owner, name, &self.client)
+    }
+
+    /// The multireddits of the logged in user.
+    pub fn my_multireddits(&self) -> Result<Vec<MultiredditInfo>> {
+        self.client.ensure_user()?;
+
+        multireddit::list(&self.client, &format!("{}api/multi/mine", self.url))
+    }
+
+    /// Create a multireddit owned by the logged in user.
+    pub fn create_multireddit(
+        &self,
+        name: &str,
+        options: &MultiredditOptions,
+    ) -> Result<Multireddit<T>> {
+        if options.visibility == Visibility::Other {
+            return Err(Error::UnknownValueError(String::from(
+                "the multireddit visibility is unknown",
+            )));
+        }
+
+        let user = self.me()?.name;
+
+        self.client.post(
+            &format!("{}api/multi/user/{}/m/{}", self.url, user, name),
+            &[("model", serde_json::to_string(options)?)],
+        )?;
+
+        Ok(Multireddit::create(&user, name, &self.client))
+    }
+
     /// Posts from the frontpage.
     pub fn frontpage(&self) -> Subreddit<T> {
         Subreddit {
             name: String::from("frontpage"),
-            url: self.url.clone(),
-            client: &self.client,
+            url: self.url.trim_end_matches('/').to_string(),
+            params: Params::default(),
+            client: self.client.clone(),
         }
     }
 
+    /// Look up posts, comments and subreddits by their fullnames, in the given order.
+    /// Things that do not exist are left out. Reddit is asked for up to 100 things at a time.
+    /// ```no_run
+    /// # fn main() -> snew::reddit::Result<()> {
+    /// # use snew::{reddit::Reddit, auth::{ApplicationAuthenticator}};
+    /// # let reddit = Reddit::new(
+    /// #    ApplicationAuthenticator::new("client_id", "client_secret"),
+    /// #    "<Operating system>:snew:v0.1.0 (by u/<reddit username>)"
+    /// #    ).unwrap();
+    /// use snew::things::{Fullname, Info};
+    ///
+    /// let fullnames = ["t3_abc123", "t1_def456", "t5_2qh1i"]
+    ///     .iter()
+    ///     .map(|fullname| fullname.parse())
+    ///     .collect::<snew::reddit::Result<Vec<Fullname>>>()?;
+    ///
+    /// for thing in reddit.info(&fullnames) {
+    ///     match thing? {
+    ///         Info::Post(post) => println!("Post: {} ({} upvotes)", post.data.title, post.data.ups),
+    ///         Info::Comment(comment) => println!("Comment: {}", comment.data.body),
+    ///         Info::Subreddit(subreddit) => println!("Subreddit: {}", subreddit.display_name),
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn info(&self, fullnames: &[Fullname]) -> InfoFeed<T> {
+        InfoFeed::new(fullnames.to_vec(), &self.client)
+    }
+
+    /// Posts that link to `url`.
+    pub fn info_by_url(&self, url: &str) -> PostFeed<T> {
+        Feed::new(format!("{}api/info", self.url), &self.client).param("url", url)
+    }
+
+    /// Search for posts across all of Reddit.
+    /// See [`Subreddit::search`] for searching within a single subreddit, and [`PostFeed`] for the available search options.
+    pub fn search(&self, query: &str) -> PostFeed<T> {
+        Feed::new(format!("{}search", self.url), &self.client).param("q", query)
+    }
+
+    /// Search for subreddits by name and description.
+    /// ```no_run
+    /// # fn main() -> snew::reddit::Result<()> {
+    /// # use snew::{reddit::Reddit, auth::{ApplicationAuthenticator}};
+    /// # let reddit = Reddit::new(
+    /// #    ApplicationAuthenticator::new("client_id", "client_secret"),
+    /// #    "<Operating system>:snew:v0.1.0 (by u/<reddit username>)"
+    /// #    ).unwrap();
+    /// for subreddit in reddit.search_subreddits("programming").take(5) {
+    ///     let subreddit = subreddit?;
+    ///     println!("{}: {:?} subscribers", subreddit.display_name, subreddit.subscribers);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn search_subreddits(&self, query: &str) -> Feed<T, SubredditInfo> {
+        Feed::new(format!("{}subreddits/search", self.url), &self.client).param("q", query)
+    }
+
+    /// Subreddits whose name starts with `prefix`, as suggested by the Reddit search bar.
+    /// Reddit returns at most 10 suggestions.
+    pub fn subreddit_autocomplete(
+        &self,
+        prefix: &str,
+        include_nsfw: bool,
+    ) -> Result<Vec<SubredditInfo>> {
+        let text = self
+            .client
+            .get(
+                &format!("{}api/subreddit_autocomplete_v2", self.url),
+                Some(&[
+                    ("query", prefix),
+                    (
+                        "include_over_18",
+                        if include_nsfw { "true" } else { "false" },
+                    ),
+                    ("include_profiles", "false"),
+                    ("limit", "10"),
+                ]),
+            )?
+            .text()?;
+
+        let listing: RawListing<RawKind<SubredditInfo>> = serde_json::from_str(&text)?;
+
+        Ok(listing
+            .data
+            .children
+            .into_iter()
+            .map(|raw| raw.data)
+            .collect())
+    }
+
+    /// The subreddits the logged in user is subscribed to.
+    pub fn my_subreddits(&self) -> Feed<T, SubredditInfo> {
+        self.mine("subscriber")
+    }
+
+    /// The subreddits the logged in user is an approved user in.
+    pub fn my_contributor_subreddits(&self) -> Feed<T, SubredditInfo> {
+        self.mine("contributor")
+    }
+
+    /// The subreddits the logged in user moderates.
+    pub fn my_moderator_subreddits(&self) -> Feed<T, SubredditInfo> {
+        self.mine("moderator")
+    }
+
+    fn mine(&self, relationship: &str) -> Feed<T, SubredditInfo> {
+        Feed::new(
+            format!("{}subreddits/mine/{}", self.url, relationship),
+            &self.client,
+        )
+    }
+
     // /// Submit a text post.
     // /// Equivalent to calling [`Subreddit::submit`], prefer using that if you already have a handle into the subreddit.
     // pub fn submit(&self, subreddit: &str, title: &str, text: &str) -> Post<T> {
@@ -153,4 +466,51 @@ pub enum Error {
     /// This error occurs if you attempt to make some request that requires you to be logged in (e.g. script authentication), but you are authenticated anonymously.
     #[error("This action is only allowed when logged in, not with anonymous authentication.")]
     NotLoggedInError,
+
+    /// Reddit understood the request, but refused to perform it, e.g. because a user does not exist.
+    #[error("Reddit returned an error.\nReason:\t{0}")]
+    APIError(String),
+
+    /// A fullname, ID or URL given to [`crate::things::Fullname`] was invalid.
+    #[error("Invalid fullname.\nReason:\t{0}")]
+    FullnameParseError(String),
+
+    /// A value snew does not know, and so can not send back to Reddit, e.g. a [`crate::things::SubredditType::Other`].
+    #[error("Can not send a value unknown to snew to Reddit.\nReason:\t{0}")]
+    UnknownValueError(String),
+}
+
+// The logged in user. Also used by handles that only have the client, such as multireddits.
+pub(crate) fn me<T: Authenticator>(client: &AuthenticatedClient<T>, url: &str) -> Result<Me> {
+    client.ensure_user()?;
+
+    Ok(serde_json::from_str(
+        &client
+            .get(&format!("{}{}", url, "api/v1/me"), None::<&()>)?
+            .text()?,
+    )?)
+}
+
+// Endpoints called with api_type=json report errors in the body, even though the status code is 200 OK.
+pub(crate) fn check_api_errors(text: &str) -> Result<()> {
+    #[derive(Deserialize)]
+    struct Response {
+        json: Option<Json>,
+    }
+
+    #[derive(Deserialize)]
+    struct Json {
+        #[serde(default)]
+        errors: Vec<(String, String, Option<String>)>,
+    }
+
+    let response: Response = serde_json::from_str(text)?;
+
+    match response
+        .json
+        .and_then(|json| json.errors.into_iter().next())
+    {
+        Some((code, message, _)) => Err(Error::APIError(format!("{}: {}", code, message))),
+        None => Ok(()),
+    }
 }
@@ -0,0 +1,447 @@
+//! The rules of subreddits, removal reasons and post requirements.
+use std::{collections::HashMap, time::SystemTime};
+
+use serde::Deserialize;
+
+use crate::{
+    auth::{AuthenticatedClient, Authenticator},
+    reddit::{check_api_errors, Error, Result, URL},
+    things::raw,
+};
+
+/// A handle to the rules of a subreddit. Use [`crate::things::Subreddit::rules`] to create one.
+/// Everything except listing the rules requires the logged in user to moderate the subreddit.
+/// ```no_run
+/// # fn main() -> snew::reddit::Result<()> {
+/// # use snew::{reddit::Reddit, auth::{ScriptAuthenticator, Credentials}};
+/// # let reddit = Reddit::new(
+/// #    ScriptAuthenticator::new(Credentials::new("client_id", "client_secret", "username", "password")),
+/// #    "<Operating system>:snew:v0.1.0 (by u/<reddit username>)"
+/// #    ).unwrap();
+/// use snew::rules::{RuleKind, RuleOptions};
+///
+/// let rules = reddit.subreddit("mysubreddit").rules();
+///
+/// rules.add(&RuleOptions {
+///     kind: RuleKind::Link,
+///     short_name: String::from("No memes"),
+///     description: String::from("Memes belong in r/mysubredditmemes"),
+///     violation_reason: None,
+/// })?;
+///
+/// for rule in rules.list()? {
+///     println!("{}. {}", rule.priority + 1, rule.short_name);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct SubredditRules<T: Authenticator> {
+    /// The name of the subreddit.
+    pub subreddit: String,
+    client: AuthenticatedClient<T>,
+}
+
+impl<T: Authenticator> SubredditRules<T> {
+    /// Create a instance of subreddit rules
+    /// Use [`crate::things::Subreddit::rules()`] instead.
+    pub fn create(subreddit: &str, client: &AuthenticatedClient<T>) -> Self {
+        Self {
+            subreddit: String::from(subreddit),
+            client: client.clone(),
+        }
+    }
+
+    /// The rules, in order.
+    pub fn list(&self) -> Result<Vec<Rule>> {
+        #[derive(Deserialize)]
+        struct Rules {
+            rules: Vec<Rule>,
+        }
+
+        let text = self
+            .client
+            .get(
+                &format!("{}r/{}/about/rules", URL, self.subreddit),
+                None::<&()>,
+            )?
+            .text()?;
+
+        let mut rules = serde_json::from_str::<Rules>(&text)?.rules;
+        rules.sort_by_key(|rule| rule.priority);
+
+        Ok(rules)
+    }
+
+    /// Add a rule, after the existing rules.
+    pub fn add(&self, rule: &RuleOptions) -> Result<()> {
+        let mut form = rule.form()?;
+        form.push(("r", self.subreddit.as_str()));
+
+        self.post("add_subreddit_rule", &form)
+    }
+
+    /// Replace the rule with the short name `old_short_name`.
+    pub fn update(&self, old_short_name: &str, rule: &RuleOptions) -> Result<()> {
+        let mut form = rule.form()?;
+        form.push(("r", self.subreddit.as_str()));
+        form.push(("old_short_name", old_short_name));
+
+        self.post("update_subreddit_rule", &form)
+    }
+
+    /// Delete the rule with the given short name.
+    pub fn delete(&self, short_name: &str) -> Result<()> {
+        self.post(
+            "remove_subreddit_rule",
+            &[
+                ("api_type", "json"),
+                ("r", &self.subreddit),
+                ("short_name", short_name),
+            ],
+        )
+    }
+
+    /// Change the order of the rules. `short_names` must contain the short name of every rule, in the new order.
+    pub fn reorder(&self, short_names: &[&str]) -> Result<()> {
+        self.post(
+            "reorder_subreddit_rules",
+            &[
+                ("api_type", "json"),
+                ("r", &self.subreddit),
+                ("new_rule_order", &short_names.join(",")),
+            ],
+        )
+    }
+
+    fn post(&self, path: &str, form: &[(&str, &str)]) -> Result<()> {
+        self.client.ensure_user()?;
+
+        let text = self
+            .client
+            .post(&format!("{}api/{}", URL, path), form)?
+            .text()?;
+
+        check_api_errors(&text)
+    }
+}
+
+/// A rule of a subreddit. See [`SubredditRules::list`].
+#[derive(Debug, Clone, Deserialize)]
+pub struct Rule {
+    /// What the rule applies to.
+    pub kind: RuleKind,
+    /// The name of the rule, which also identifies it.
+    pub short_name: String,
+    /// The full description of the rule, in markdown.
+    pub description: String,
+    /// The reason shown when reporting something for breaking this rule. Defaults to the short name.
+    pub violation_reason: Option<String>,
+    /// The position of the rule, starting at 0.
+    pub priority: i32,
+    #[serde(with = "raw::timestamp")]
+    pub created_utc: SystemTime,
+}
+
+/// What a rule applies to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RuleKind {
+    /// Both posts and comments.
+    All,
+    /// Only posts.
+    Link,
+    /// Only comments.
+    Comment,
+    /// A kind not known to snew. Can not be used in [`RuleOptions`].
+    #[serde(other)]
+    Other,
+}
+
+impl RuleKind {
+    fn as_str(&self) -> Option<&'static str> {
+        match self {
+            Self::All => Some("all"),
+            Self::Link => Some("link"),
+            Self::Comment => Some("comment"),
+            Self::Other => None,
+        }
+    }
+}
+
+/// A new or updated rule. See [`SubredditRules::add`].
+#[derive(Debug, Clone)]
+pub struct RuleOptions {
+    pub kind: RuleKind,
+    /// The name of the rule. At most 100 characters.
+    pub short_name: String,
+    /// The full description of the rule, in markdown. At most 500 characters.
+    pub description: String,
+    /// The reason shown when reporting something for breaking this rule. Defaults to the short name.
+    pub violation_reason: Option<String>,
+}
+
+impl RuleOptions {
+    fn form(&self) -> Result<Vec<(&str, &str)>> {
+        let kind = self
+            .kind
+            .as_str()
+            .ok_or_else(|| Error::UnknownValueError(String::from("the rule kind is unknown")))?;
+
+        let mut form = vec![
+            ("api_type", "json"),
+            ("kind", kind),
+            ("short_name", self.short_name.as_str()),
+            ("description", self.description.as_str()),
+        ];
+        form.extend(
+            self.violation_reason
+                .as_deref()
+                .map(|reason| ("violation_reason", reason)),
+        );
+        Ok(form)
+    }
+}
+
+/// A handle to the removal reasons of a subreddit. Use [`crate::things::Subreddit::removal_reasons`] to create one.
+/// See also [`crate::moderation::PostModeration::add_removal_reason`].
+#[derive(Debug)]
+pub struct RemovalReasons<T: Authenticator> {
+    /// The name of the subreddit.
+    pub subreddit: String,
+    client: AuthenticatedClient<T>,
+}
+
+impl<T: Authenticator> RemovalReasons<T> {
+    /// Create a instance of removal reasons
+    /// Use [`crate::things::Subreddit::removal_reasons()`] instead.
+    pub fn create(subreddit: &str, client: &AuthenticatedClient<T>) -> Self {
+        Self {
+            subreddit: String::from(subreddit),
+            client: client.clone(),
+        }
+    }
+
+    /// The removal reasons, in order.
+    pub fn list(&self) -> Result<Vec<RemovalReason>> {
+        #[derive(Deserialize)]
+        struct Reasons {
+            data: HashMap<String, RemovalReason>,
+            order: Vec<String>,
+        }
+
+        self.client.ensure_user()?;
+
+        let text = self.client.get(&self.url(""), None::<&()>)?.text()?;
+
+        let Reasons { mut data, order } = serde_json::from_str(&text)?;
+
+        Ok(order.iter().filter_map(|id| data.remove(id)).collect())
+    }
+
+    /// Add a removal reason, returning its id.
+    pub fn add(&self, title: &str, message: &str) -> Result<String> {
+        #[derive(Deserialize)]
+        struct Created {
+            id: String,
+        }
+
+        self.client.ensure_user()?;
+
+        let text = self
+            .client
+            .post(&self.url(""), &[("title", title), ("message", message)])?
+            .text()?;
+
+        Ok(serde_json::from_str::<Created>(&text)?.id)
+    }
+
+    /// Replace the title and message of a removal reason.
+    pub fn update(&self, id: &str, title: &str, message: &str) -> Result<()> {
+        self.client.ensure_user()?;
+
+        self.client.put(
+            &self.url(&format!("/{}", id)),
+            &[("title", title), ("message", message)],
+        )?;
+
+        Ok(())
+    }
+
+    /// Delete a removal reason.
+    pub fn delete(&self, id: &str) -> Result<()> {
+        self.client.ensure_user()?;
+
+        self.client.delete(&self.url(&format!("/{}", id)))?;
+
+        Ok(())
+    }
+
+    fn url(&self, path: &str) -> String {
+        format!("{}api/v1/{}/removal_reasons{}", URL, self.subreddit, path)
+    }
+}
+
+/// A removal reason of a subreddit. See [`RemovalReasons::list`].
+#[derive(Debug, Clone, Deserialize)]
+pub struct RemovalReason {
+    pub id: String,
+    pub title: String,
+    /// The message sent to the author of the removed post or comment, in markdown.
+    pub message: String,
+}
+
+/// The requirements posts in a subreddit must meet. See [`crate::things::Subreddit::post_requirements`].
+#[derive(Debug, Clone, Deserialize)]
+pub struct PostRequirements {
+    pub title_text_min_length: Option<usize>,
+    pub title_text_max_length: Option<usize>,
+    /// The title must contain at least one of these, if any.
+    #[serde(default)]
+    pub title_required_strings: Vec<String>,
+    /// The title must not contain any of these.
+    #[serde(default)]
+    pub title_blacklisted_strings: Vec<String>,
+    /// The title must match these regular expressions. Not checked by [`Self::check`].
+    #[serde(default)]
+    pub title_regexes: Vec<String>,
+    /// Whether posts must, or must not, have a body.
+    pub body_restriction_policy: Option<BodyRestriction>,
+    pub body_text_min_length: Option<usize>,
+    pub body_text_max_length: Option<usize>,
+    /// The body must contain at least one of these, if any.
+    #[serde(default)]
+    pub body_required_strings: Vec<String>,
+    /// The body must not contain any of these.
+    #[serde(default)]
+    pub body_blacklisted_strings: Vec<String>,
+    /// The body must match these regular expressions. Not checked by [`Self::check`].
+    #[serde(default)]
+    pub body_regexes: Vec<String>,
+    /// Links to these domains are not allowed.
+    #[serde(default)]
+    pub domain_blacklist: Vec<String>,
+    /// If not empty, only links to these domains are allowed.
+    #[serde(default)]
+    pub domain_whitelist: Vec<String>,
+    /// Whether posts must have flair.
+    #[serde(default)]
+    pub is_flair_required: bool,
+    /// Guidelines shown when submitting, in markdown.
+    pub guidelines_text: Option<String>,
+}
+
+/// Whether posts must, or must not, have a body. See [`PostRequirements::body_restriction_policy`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum BodyRestriction {
+    None,
+    Required,
+    NotAllowed,
+    /// A policy not known to snew.
+    #[serde(other)]
+    Other,
+}
+
+/// A way a post does not meet the requirements of a subreddit. See [`PostRequirements::check`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Violation {
+    TitleTooShort,
+    TitleTooLong,
+    /// The title does not contain any of the required strings.
+    TitleMissingRequiredString,
+    /// The title contains this disallowed string.
+    TitleContains(String),
+    BodyRequired,
+    BodyNotAllowed,
+    BodyTooShort,
+    BodyTooLong,
+    /// The body does not contain any of the required strings.
+    BodyMissingRequiredString,
+    /// The body contains this disallowed string.
+    BodyContains(String),
+}
+
+impl PostRequirements {
+    /// Check a text post against these requirements, before submitting it. Returns all violations, if any.
+    /// Strings are compared case insensitively, and regular expressions are not checked.
+    // Option::is_some_and needs Rust 1.70.
+    #[allow(clippy::unnecessary_map_or)]
+    pub fn check(&self, title: &str, body: &str) -> Vec<Violation> {
+        let mut violations = Vec::new();
+
+        let title_length = title.chars().count();
+        if self
+            .title_text_min_length
+            .map_or(false, |min| title_length < min)
+        {
+            violations.push(Violation::TitleTooShort);
+        }
+        if self
+            .title_text_max_length
+            .map_or(false, |max| title_length > max)
+        {
+            violations.push(Violation::TitleTooLong);
+        }
+        if !contains_any(title, &self.title_required_strings) {
+            violations.push(Violation::TitleMissingRequiredString);
+        }
+        violations.extend(
+            blacklisted(title, &self.title_blacklisted_strings).map(Violation::TitleContains),
+        );
+
+        match self.body_restriction_policy {
+            Some(BodyRestriction::Required) if body.trim().is_empty() => {
+                violations.push(Violation::BodyRequired)
+            }
+            Some(BodyRestriction::NotAllowed) if !body.trim().is_empty() => {
+                violations.push(Violation::BodyNotAllowed)
+            }
+            _ => {}
+        }
+
+        if !body.is_empty() {
+            let body_length = body.chars().count();
+            if self
+                .body_text_min_length
+                .map_or(false, |min| body_length < min)
+            {
+                violations.push(Violation::BodyTooShort);
+            }
+            if self
+                .body_text_max_length
+                .map_or(false, |max| body_length > max)
+            {
+                violations.push(Violation::BodyTooLong);
+            }
+            if !contains_any(body, &self.body_required_strings) {
+                violations.push(Violation::BodyMissingRequiredString);
+            }
+            violations.extend(
+                blacklisted(body, &self.body_blacklisted_strings).map(Violation::BodyContains),
+            );
+        }
+
+        violations
+    }
+}
+
+// Whether the text contains any of the required strings. True if there are none.
+fn contains_any(text: &str, required: &[String]) -> bool {
+    let text = text.to_lowercase();
+
+    required.is_empty()
+        || required
+            .iter()
+            .any(|required| text.contains(&required.to_lowercase()))
+}
+
+// The disallowed strings that the text contains.
+fn blacklisted<'b>(text: &str, disallowed: &'b [String]) -> impl Iterator<Item = String> + 'b {
+    let text = text.to_lowercase();
+
+    disallowed
+        .iter()
+        .filter(move |disallowed| text.contains(&disallowed.to_lowercase()))
+        .cloned()
+}
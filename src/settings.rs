@@ -0,0 +1,132 @@
+//! The settings of subreddits.
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::{
+    reddit::{Error, Result},
+    things::{CommentSort, SubmissionType, SubredditType},
+};
+
+/// The settings of a subreddit. See [`crate::things::Subreddit::settings`].
+///
+/// Reddit replaces all settings when updating, so only update settings you got from Reddit, to not reset anything by accident.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SubredditSettings {
+    /// The fullname of the subreddit.
+    pub subreddit_id: String,
+    pub title: String,
+    /// The short description shown in search results and on the subreddit.
+    pub public_description: String,
+    /// The sidebar, in markdown.
+    pub description: String,
+    /// Text shown on the submission page, in markdown.
+    pub submit_text: String,
+    /// Custom text for the button to submit a link post.
+    pub submit_link_label: Option<String>,
+    /// Custom text for the button to submit a text post.
+    pub submit_text_label: Option<String>,
+    /// Who can view and post in the subreddit.
+    pub subreddit_type: SubredditType,
+    /// What kind of posts are allowed.
+    pub content_options: SubmissionType,
+    /// The language of the subreddit, e.g. `en`.
+    pub language: String,
+    /// Whether the subreddit is marked NSFW.
+    pub over_18: bool,
+    pub spoilers_enabled: bool,
+    pub allow_images: bool,
+    pub allow_videos: bool,
+    pub allow_polls: bool,
+    /// Whether to show thumbnails of media.
+    pub show_media: bool,
+    /// Whether to expand media previews on the comments page.
+    pub show_media_preview: bool,
+    /// Whether to show deleted and removed comments that have no replies as collapsed.
+    pub collapse_deleted_comments: bool,
+    /// How many minutes to hide comment scores for.
+    pub comment_score_hide_mins: i32,
+    /// The default sort order of comments. None to use the Reddit default.
+    pub suggested_comment_sort: Option<CommentSort>,
+    /// Whether the subreddit can be included in r/all and recommended to users.
+    pub default_set: bool,
+    pub allow_discovery: bool,
+    /// How aggressive the spam filter is for link posts, one of `low`, `high` or `all`.
+    pub spam_links: String,
+    /// How aggressive the spam filter is for text posts, one of `low`, `high` or `all`.
+    pub spam_selfposts: String,
+    /// How aggressive the spam filter is for comments, one of `low`, `high` or `all`.
+    pub spam_comments: String,
+    /// Who can edit the wiki, one of `disabled`, `modonly` or `anyone`.
+    pub wikimode: String,
+    /// How many days old an account must be to edit the wiki.
+    pub wiki_edit_age: Option<i32>,
+    /// How much karma an account must have to edit the wiki.
+    pub wiki_edit_karma: Option<i32>,
+    /// Whether to send a welcome message to new subscribers.
+    #[serde(default)]
+    pub welcome_message_enabled: bool,
+    /// The welcome message, in markdown.
+    pub welcome_message_text: Option<String>,
+    /// Whether to exclude posts and comments by banned users from the modqueue.
+    #[serde(default)]
+    pub exclude_banned_modqueue: bool,
+    /// Whether users can write their own report reasons.
+    #[serde(default)]
+    pub free_form_reports: bool,
+    /// All other settings, as returned by Reddit.
+    #[serde(flatten)]
+    pub other: serde_json::Map<String, Value>,
+}
+
+impl SubredditSettings {
+    // Turn the settings into form fields for /api/site_admin, which uses different names for some settings than /about/edit.
+    pub(crate) fn to_form(&self) -> Result<Vec<(String, String)>> {
+        if self.subreddit_type == SubredditType::Other {
+            return Err(Error::UnknownValueError(String::from(
+                "the subreddit type is unknown",
+            )));
+        }
+        if self.content_options == SubmissionType::Other {
+            return Err(Error::UnknownValueError(String::from(
+                "the submission type is unknown",
+            )));
+        }
+        if self.suggested_comment_sort == Some(CommentSort::Other) {
+            return Err(Error::UnknownValueError(String::from(
+                "the suggested comment sort is unknown",
+            )));
+        }
+
+        let value = serde_json::to_value(self)?;
+
+        let fields = match value {
+            Value::Object(fields) => fields,
+            _ => serde_json::Map::new(),
+        };
+
+        Ok(fields
+            .into_iter()
+            .filter_map(|(key, value)| {
+                let value = match value {
+                    Value::String(value) => value,
+                    Value::Bool(value) => value.to_string(),
+                    Value::Number(value) => value.to_string(),
+                    // Null values are left out, lists and objects can not be sent as form fields.
+                    Value::Null | Value::Array(_) | Value::Object(_) => return None,
+                };
+
+                let key = match key.as_str() {
+                    "subreddit_id" => String::from("sr"),
+                    "subreddit_type" => String::from("type"),
+                    "content_options" => String::from("link_type"),
+                    "language" => String::from("lang"),
+                    "default_set" => String::from("allow_top"),
+                    "header_hover_text" => String::from("header-title"),
+                    _ => key,
+                };
+
+                Some((key, value))
+            })
+            .collect())
+    }
+}
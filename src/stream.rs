@@ -0,0 +1,186 @@
+//! Streams of new things, such as new posts in a subreddit, that keep yielding things as they are created.
+use std::{
+    collections::{HashSet, VecDeque},
+    thread,
+    time::Duration,
+};
+
+use crate::{
+    auth::{AuthenticatedClient, Authenticator},
+    inbox::Message,
+    reddit::Result,
+    things::{Comment, Feed, FromListing, Fullname, Params, Post},
+};
+
+/// Polls a listing for new things, and yields each new thing once, oldest first. This iterator never ends.
+/// If there is nothing new, the stream waits longer between each request, up to [`Self::max_delay`].
+/// The errors are either from the HTTP request or the JSON parsing. The stream can still be used after an error.
+/// ```no_run
+/// # fn main() -> snew::reddit::Result<()> {
+/// # use snew::{reddit::Reddit, auth::{ApplicationAuthenticator}};
+/// # let reddit = Reddit::new(
+/// #    ApplicationAuthenticator::new("client_id", "client_secret"),
+/// #    "<Operating system>:snew:v0.1.0 (by u/<reddit username>)"
+/// #    ).unwrap();
+/// for post in reddit.subreddit("rust").stream_submissions().skip_existing(true) {
+///     println!("New post: {}", post?.data.title);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct Stream<T: Authenticator, I> {
+    /// The shortest time to wait between requests. Used whenever the last request returned something new.
+    pub min_delay: Duration,
+    /// The longest time to wait between requests.
+    pub max_delay: Duration,
+    url: String,
+    params: Params,
+    client: AuthenticatedClient<T>,
+    seen: BoundedSet,
+    cached: VecDeque<I>,
+    delay: Duration,
+    // Whether the next successful poll only marks things as seen, without yielding them.
+    skip_existing: bool,
+    started: bool,
+}
+
+impl<T: Authenticator, I: Streamable<T>> Stream<T, I> {
+    pub(crate) fn new(url: String, client: &AuthenticatedClient<T>) -> Self {
+        Self {
+            min_delay: Duration::from_secs(1),
+            max_delay: Duration::from_secs(16),
+            url,
+            params: Params::default(),
+            client: client.clone(),
+            seen: BoundedSet::new(300),
+            cached: VecDeque::new(),
+            delay: Duration::from_secs(1),
+            skip_existing: false,
+            started: false,
+        }
+    }
+
+    // Set query parameters sent with every request, e.g. those of a subreddit.
+    pub(crate) fn with_params(mut self, params: &Params) -> Self {
+        self.params = params.clone();
+        self
+    }
+
+    /// Whether to skip the things that already exist when the stream starts, and only yield things created after that.
+    /// By default, the stream starts by yielding up to 100 of the most recent things.
+    pub fn skip_existing(mut self, skip: bool) -> Self {
+        self.skip_existing = skip;
+        self
+    }
+
+    // Fetch the newest things, and cache the ones not seen before.
+    fn poll(&mut self) -> Result<()> {
+        let newest = Feed::<T, I>::new(self.url.clone(), &self.client)
+            .with_params(&self.params)
+            .take(100)
+            .collect::<Result<Vec<_>>>()?;
+
+        let skip = self.skip_existing;
+        self.skip_existing = false;
+
+        let before = self.cached.len();
+
+        // Listings are newest first, but the stream yields oldest first.
+        for thing in newest.into_iter().rev() {
+            if self.seen.insert(thing.fullname()) && !skip {
+                self.cached.push_back(thing);
+            }
+        }
+
+        if self.cached.len() > before {
+            self.delay = self.min_delay;
+        } else {
+            self.delay = (self.delay * 2).min(self.max_delay);
+        }
+
+        Ok(())
+    }
+}
+
+impl<T: Authenticator, I: Streamable<T>> Iterator for Stream<T, I> {
+    type Item = Result<I>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(thing) = self.cached.pop_front() {
+                return Some(Ok(thing));
+            }
+
+            if self.started {
+                thread::sleep(self.delay);
+            }
+            self.started = true;
+
+            if let Err(error) = self.poll() {
+                self.delay = (self.delay * 2).min(self.max_delay);
+                return Some(Err(error));
+            }
+        }
+    }
+}
+
+/// Something that can be streamed, i.e. a listing entry with a unique fullname.
+pub trait Streamable<T: Authenticator>: FromListing<T> {
+    /// The fullname, e.g. `t3_1a2b3c`.
+    fn fullname(&self) -> Fullname;
+}
+
+impl<T: Authenticator> Streamable<T> for Post<T> {
+    fn fullname(&self) -> Fullname {
+        Post::fullname(self)
+    }
+}
+
+impl<T: Authenticator> Streamable<T> for Comment<T> {
+    fn fullname(&self) -> Fullname {
+        Comment::fullname(self)
+    }
+}
+
+impl<T: Authenticator> Streamable<T> for Message<T> {
+    fn fullname(&self) -> Fullname {
+        self.name.clone()
+    }
+}
+
+// A set that forgets the oldest entries once it is full, so a stream that runs forever does not grow forever.
+#[derive(Debug)]
+pub(crate) struct BoundedSet {
+    capacity: usize,
+    set: HashSet<Fullname>,
+    order: VecDeque<Fullname>,
+}
+
+impl BoundedSet {
+    pub(crate) fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            set: HashSet::with_capacity(capacity),
+            order: VecDeque::with_capacity(capacity),
+        }
+    }
+
+    // Returns whether the value was not already in the set.
+    pub(crate) fn insert(&mut self, value: Fullname) -> bool {
+        if self.set.contains(&value) {
+            return false;
+        }
+
+        if self.order.len() == self.capacity {
+            if let Some(oldest) = self.order.pop_front() {
+                self.set.remove(&oldest);
+            }
+        }
+
+        self.set.insert(value.clone());
+        self.order.push_back(value);
+
+        true
+    }
+}
@@ -1,11 +1,66 @@
 #[cfg(test)]
+#[allow(clippy::module_inception)]
 mod tests {
     use crate::{
-        auth::{ApplicationAuthenticator, Credentials, ScriptAuthenticator},
-        reddit::{Reddit, Result},
+        auth::{
+            ApplicationAuthenticator, AuthenticatedClient, Authenticator, Credentials,
+            ScriptAuthenticator, Token,
+        },
+        flair::{csv_field, Flair, FlairRichtext},
+        inbox::Message,
+        media::{gallery, Image},
+        moderation::{
+            ban_form, permissions, removal_reason_json, BanOptions, ModAction, ModPermission,
+            Relationship,
+        },
+        modmail::{ConversationStatus, ModmailAction},
+        multireddit::{MultiredditInfo, MultiredditOptions, Visibility},
+        reddit::{check_api_errors, Error, Reddit, Result},
+        rules::{BodyRestriction, PostRequirements, Rule, RuleKind, Violation},
+        settings::SubredditSettings,
+        stream::{BoundedSet, Stream},
+        things::{
+            id_to_number,
+            raw::{
+                comment::RawCommentData, generic_kind::RawKind, listing::RawListing,
+                message::RawMessageData, modmail::RawConversationDetails,
+                multireddit::RawMultireddit, post::RawPostData, wiki::RawWikiPageSettings, RawInfo,
+                RawThing,
+            },
+            AcceptPms, Comment, CommentData, CommentSort, Distinguished, Edited, FromListing,
+            Fullname, InfoFeed, Kind, Me, Post, PostData, PostFeed, Preferences, RedditorInfo,
+            SearchSort, SubmissionType, Subreddit, SubredditInfo, SubredditType, Thing, TimeFilter,
+            UserSort,
+        },
+        wiki::{settings_form, WikiPageSettings, WikiPermLevel},
     };
 
-    use std::env;
+    use std::{
+        collections::HashSet,
+        convert::TryFrom,
+        env,
+        time::{Duration, UNIX_EPOCH},
+    };
+
+    // Pretends to be logged in, so things that need a client can be made without a connection.
+    struct OfflineAuthenticator;
+
+    impl Authenticator for OfflineAuthenticator {
+        fn login(&mut self) -> Result<()> {
+            Ok(())
+        }
+
+        fn token(&self) -> Option<Token> {
+            serde_json::from_str(
+                r#"{"access_token": "offline", "expires_in": 3600, "scope": "*", "token_type": "bearer"}"#,
+            )
+            .ok()
+        }
+
+        fn is_user(&self) -> bool {
+            true
+        }
+    }
 
     #[test]
     fn it_works() -> Result<()> {
@@ -32,12 +87,40 @@ mod tests {
 
         for post in reddit.subreddit("rust").hot().take(1) {
             let post = post?;
-            println!("Post: {:?}", post.title);
+            println!("Post: {:?}", post.data.title);
         }
 
         for post in reddit.frontpage().best().take(1) {
             let post = post?;
-            println!("Frontpage post: {}", post.title);
+            println!("Frontpage post: {}", post.data.title);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn combined_subreddits() -> Result<()> {
+        let application_auth = ApplicationAuthenticator::new(
+            &env::var("REDDIT_CLIENT_ID").unwrap(),
+            &env::var("REDDIT_CLIENT_SECRET").unwrap(),
+        );
+        let reddit = Reddit::new(application_auth, "Windows:snew:v0.1.0 (by /u/zower98)").unwrap();
+
+        let languages = reddit.subreddits(&["rust", "golang"]);
+        assert_eq!(languages.name, "rust+golang");
+        for post in languages.new().take(5) {
+            let post = post?;
+            assert!(post.data.subreddit == "rust" || post.data.subreddit == "golang");
+        }
+
+        let all = reddit.all().exclude(&["rust"]);
+        assert_eq!(all.name, "all-rust");
+        for post in all.hot().take(5) {
+            assert_ne!(post?.data.subreddit, "rust");
+        }
+
+        for post in reddit.popular(Some("GLOBAL")).hot().take(1) {
+            println!("Popular post: {}", post?.data.title);
         }
 
         Ok(())
@@ -58,17 +141,1427 @@ mod tests {
 
         for post in hot.take(3) {
             let post = post?;
-            println!("Post: {}", post.title);
+            println!("Post: {}", post.data.title);
 
             for comment in post.comments().take(1) {
                 let comment = comment?;
-                println!("By: {}, {}", comment.author, comment.body);
+                println!("By: {}, {}", comment.data.author, comment.data.body);
+            }
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn search() -> Result<()> {
+        let application_auth = ApplicationAuthenticator::new(
+            &env::var("REDDIT_CLIENT_ID").unwrap(),
+            &env::var("REDDIT_CLIENT_SECRET").unwrap(),
+        );
+        let reddit = Reddit::new(application_auth, "Windows:snew:v0.1.0 (by /u/zower98)").unwrap();
+
+        for post in reddit
+            .subreddit("rust")
+            .search("lifetimes")
+            .sort(SearchSort::New)
+            .take(3)
+        {
+            let post = post?;
+            assert_eq!(post.data.subreddit, "rust");
+            println!("Search result: {}", post.data.title);
+        }
+
+        for post in reddit.search("rust").time(TimeFilter::Week).take(3) {
+            let post = post?;
+            println!("Site-wide search result: {}", post.data.title);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn subreddit_discovery() -> Result<()> {
+        let application_auth = ApplicationAuthenticator::new(
+            &env::var("REDDIT_CLIENT_ID").unwrap(),
+            &env::var("REDDIT_CLIENT_SECRET").unwrap(),
+        );
+        let reddit = Reddit::new(application_auth, "Windows:snew:v0.1.0 (by /u/zower98)").unwrap();
+
+        for subreddit in reddit.search_subreddits("rust programming").take(3) {
+            let subreddit = subreddit?;
+            println!("Found: {}", subreddit.display_name);
+        }
+
+        let suggestions = reddit.subreddit_autocomplete("rus", false)?;
+        assert!(suggestions.iter().all(|subreddit| !subreddit.over18));
+
+        Ok(())
+    }
+
+    #[test]
+    fn subreddit_about() -> Result<()> {
+        let application_auth = ApplicationAuthenticator::new(
+            &env::var("REDDIT_CLIENT_ID").unwrap(),
+            &env::var("REDDIT_CLIENT_SECRET").unwrap(),
+        );
+        let reddit = Reddit::new(application_auth, "Windows:snew:v0.1.0 (by /u/zower98)").unwrap();
+
+        let about = reddit.subreddit("rust").about()?;
+        assert_eq!(about.display_name, "rust");
+        assert_eq!(about.subreddit_type, SubredditType::Public);
+        assert_eq!(about.user_is_subscriber, None);
+
+        Ok(())
+    }
+
+    #[test]
+    fn parse_subreddit_info() {
+        let json = r#"{
+            "kind": "t5",
+            "data": {
+                "display_name": "rust",
+                "name": "t5_2s7lj",
+                "title": "The Rust Programming Language",
+                "public_description": "A place for all things related to Rust",
+                "description": "sidebar",
+                "subscribers": 250000,
+                "accounts_active": 1200,
+                "created_utc": 1291855360.0,
+                "over18": false,
+                "subreddit_type": "some_new_type",
+                "submission_type": "self",
+                "icon_img": "",
+                "community_icon": null,
+                "banner_img": "https://example.com/banner.png",
+                "url": "/r/rust/",
+                "user_is_moderator": null,
+                "user_is_subscriber": true,
+                "user_is_banned": false
+            }
+        }"#;
+
+        let info: RawKind<SubredditInfo> = serde_json::from_str(json).unwrap();
+        let info = info.data;
+
+        assert_eq!(info.active_user_count, Some(1200));
+        assert_eq!(
+            info.created_utc,
+            UNIX_EPOCH + Duration::from_secs(1291855360)
+        );
+        assert_eq!(info.subreddit_type, SubredditType::Other);
+        assert_eq!(info.submission_type, Some(SubmissionType::Text));
+        assert_eq!(info.icon_img, None);
+        assert_eq!(info.community_icon, None);
+        assert_eq!(info.banner_background_image, None);
+        assert_eq!(
+            info.banner_img.as_deref(),
+            Some("https://example.com/banner.png")
+        );
+        assert_eq!(info.user_is_subscriber, Some(true));
+
+        let value = serde_json::to_value(&info).unwrap();
+        assert_eq!(value["active_user_count"], 1200);
+        assert_eq!(value["subreddit_type"], "other");
+        assert_eq!(value["submission_type"], "self");
+        assert_eq!(value["icon_img"], serde_json::Value::Null);
+
+        let info: SubredditInfo = serde_json::from_value(value.clone()).unwrap();
+        assert_eq!(serde_json::to_value(&info).unwrap(), value);
+    }
+
+    #[test]
+    fn parse_info() {
+        let mut post = raw_post();
+        post["data"]["selftext"] = serde_json::json!("Fish & chips");
+        post["data"]["selftext_html"] = serde_json::json!(
+            "<!-- SC_OFF --><div class=\"md\"><p>Fish &amp; chips</p>\n</div><!-- SC_ON -->"
+        );
+
+        let json = serde_json::json!({
+            "kind": "Listing",
+            "data": {
+                "after": null,
+                "before": null,
+                "children": [
+                    post,
+                    {
+                        "kind": "t1",
+                        "data": {
+                            "author": "spez",
+                            "body": "Hi",
+                            "id": "def456",
+                            "parent_id": "t3_o1xu6r",
+                            "link_id": "t3_o1xu6r",
+                            "score": 1,
+                            "created_utc": 1622800000.0,
+                            "edited": false,
+                            "permalink": "/r/rust/comments/o1xu6r/announcing_rust_1530/def456/",
+                            "body_html": "<div class=\"md\"><p>Hi</p>\n</div>",
+                            "subreddit": "rust"
+                        }
+                    },
+                    {
+                        "kind": "t5",
+                        "data": {
+                            "display_name": "rust",
+                            "name": "t5_2s7lj",
+                            "title": "The Rust Programming Language",
+                            "public_description": "",
+                            "description": "",
+                            "subscribers": 250000,
+                            "created_utc": 1291855360.0,
+                            "over18": false,
+                            "subreddit_type": "public",
+                            "url": "/r/rust/"
+                        }
+                    }
+                ]
+            }
+        });
+
+        let listing: RawListing<RawInfo> = serde_json::from_value(json).unwrap();
+        let children = listing.data.children;
+
+        assert!(
+            matches!(&children[0], RawInfo::Thing(RawThing::Post(post)) if post.id == "o1xu6r")
+        );
+        // With raw_json=1 only the HTML is escaped
+        if let RawInfo::Thing(RawThing::Post(post)) = &children[0] {
+            assert_eq!(post.selftext, "Fish & chips");
+        }
+        assert!(
+            matches!(&children[1], RawInfo::Thing(RawThing::Comment(comment)) if comment.id == "def456")
+        );
+        assert!(
+            matches!(&children[2], RawInfo::Subreddit(subreddit) if subreddit.data.display_name == "rust")
+        );
+    }
+    #[test]
+    fn fullnames() {
+        let post: Fullname = "t3_abc123".parse().unwrap();
+        assert_eq!(post.kind(), Kind::Link);
+        assert_eq!(post.id(), "abc123");
+        assert_eq!(post.number(), 623698779);
+        assert_eq!(post.to_string(), "t3_abc123");
+
+        assert!("t3abc123".parse::<Fullname>().is_err());
+        assert!("t9_abc123".parse::<Fullname>().is_err());
+        assert!("t3_".parse::<Fullname>().is_err());
+        assert!("t3_ABC123".parse::<Fullname>().is_err());
+        assert!("t3_+abc".parse::<Fullname>().is_err());
+
+        assert_eq!(id_to_number("z").unwrap(), 35);
+        assert_eq!(id_to_number("10").unwrap(), 36);
+
+        let mut fullnames: Vec<Fullname> = ["t3_10", "t1_zz", "t3_z"]
+            .iter()
+            .map(|fullname| fullname.parse().unwrap())
+            .collect();
+        fullnames.sort();
+        assert_eq!(
+            fullnames
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>(),
+            vec!["t1_zz", "t3_z", "t3_10"]
+        );
+
+        // Equality agrees with the ordering, even with leading zeros
+        let padded: Fullname = "t3_0z".parse().unwrap();
+        let plain: Fullname = "t3_z".parse().unwrap();
+        assert_eq!(padded.cmp(&plain), std::cmp::Ordering::Equal);
+        assert_eq!(padded, plain);
+        assert_eq!(
+            [padded, plain]
+                .iter()
+                .cloned()
+                .collect::<HashSet<_>>()
+                .len(),
+            1
+        );
+    }
+
+    #[test]
+    fn fullname_from_url() {
+        let parse = |url| Fullname::from_url(url).unwrap().to_string();
+
+        assert_eq!(
+            parse("https://www.reddit.com/r/rust/comments/abc123/hello_world/"),
+            "t3_abc123"
+        );
+        assert_eq!(
+            parse("https://old.reddit.com/r/rust/comments/abc123/hello_world/def456/?context=3"),
+            "t1_def456"
+        );
+        assert_eq!(parse("reddit.com/comments/abc123"), "t3_abc123");
+        assert_eq!(
+            parse("https://reddit.com/r/rust/comments/abc123#comments"),
+            "t3_abc123"
+        );
+        assert_eq!(parse("https://redd.it/abc123"), "t3_abc123");
+
+        assert!(Fullname::from_url("https://www.reddit.com/r/rust/").is_err());
+        assert!(Fullname::from_url("https://notreddit.com/r/rust/comments/abc123/").is_err());
+    }
+
+    #[test]
+    fn subscriptions() -> Result<()> {
+        let script_auth = ScriptAuthenticator::new(Credentials::new(
+            &env::var("REDDIT_CLIENT_ID").unwrap(),
+            &env::var("REDDIT_CLIENT_SECRET").unwrap(),
+            &env::var("REDDIT_USERNAME").unwrap(),
+            &env::var("REDDIT_PASSWORD").unwrap(),
+        ));
+        let reddit = Reddit::new(script_auth, "Windows:snew:v0.1.0 (by /u/zower98)").unwrap();
+
+        let rust = reddit.subreddit("rust");
+        rust.subscribe()?;
+        assert_eq!(rust.about()?.user_is_subscriber, Some(true));
+
+        let subscribed = reddit
+            .my_subreddits()
+            .collect::<Result<Vec<_>>>()?
+            .into_iter()
+            .any(|subreddit| subreddit.display_name == "rust");
+        assert!(subscribed);
+
+        rust.unsubscribe()?;
+        assert_eq!(rust.about()?.user_is_subscriber, Some(false));
+
+        Ok(())
+    }
+
+    #[test]
+    fn redditor() -> Result<()> {
+        let application_auth = ApplicationAuthenticator::new(
+            &env::var("REDDIT_CLIENT_ID").unwrap(),
+            &env::var("REDDIT_CLIENT_SECRET").unwrap(),
+        );
+        let reddit = Reddit::new(application_auth, "Windows:snew:v0.1.0 (by /u/zower98)").unwrap();
+
+        let spez = reddit.redditor("spez");
+        assert!(spez.about()?.created_utc.is_some());
+
+        for thing in spez.overview().sort(UserSort::Top).take(5) {
+            match thing? {
+                Thing::Post(post) => println!("Post: {}", post.data.title),
+                Thing::Comment(comment) => println!("Comment: {}", comment.data.body),
             }
         }
 
         Ok(())
     }
 
+    #[test]
+    fn parse_suspended_redditor() {
+        let json = r#"{"kind": "t2", "data": {"name": "suspended_user", "is_suspended": true}}"#;
+
+        let info: RawKind<RedditorInfo> = serde_json::from_str(json).unwrap();
+
+        assert!(info.data.is_suspended);
+        assert_eq!(info.data.created_utc, None);
+        assert_eq!(info.data.total_karma, 0);
+    }
+
+    #[test]
+    fn me_roundtrip() {
+        let json = serde_json::json!({
+            "name": "zower98",
+            "id": "abc",
+            "created_utc": 1600000000.0,
+            "total_karma": 150,
+            "link_karma": 100,
+            "comment_karma": 50,
+            "verified": true,
+            "has_mail": false,
+            "inbox_count": 0,
+            "is_mod": true,
+            "is_gold": false,
+            "icon_img": "https://styles.redditmedia.com/icon.png"
+        });
+
+        let me: Me = serde_json::from_value(json.clone()).unwrap();
+        assert_eq!(me.created_utc, UNIX_EPOCH + Duration::from_secs(1600000000));
+
+        assert_eq!(serde_json::to_value(&me).unwrap(), json);
+    }
+
+    #[test]
+    fn karma_and_trophies() -> Result<()> {
+        let script_auth = ScriptAuthenticator::new(Credentials::new(
+            &env::var("REDDIT_CLIENT_ID").unwrap(),
+            &env::var("REDDIT_CLIENT_SECRET").unwrap(),
+            &env::var("REDDIT_USERNAME").unwrap(),
+            &env::var("REDDIT_PASSWORD").unwrap(),
+        ));
+        let reddit = Reddit::new(script_auth, "Windows:snew:v0.1.0 (by /u/zower98)").unwrap();
+
+        let me = reddit.me()?;
+        let karma: i32 = reddit
+            .karma_breakdown()?
+            .iter()
+            .map(|subreddit| subreddit.link_karma)
+            .sum();
+        println!(
+            "{} has {} link karma, {} counted",
+            me.name, me.link_karma, karma
+        );
+
+        for trophy in reddit.trophies()? {
+            println!("Trophy: {}", trophy.name);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn preferences_roundtrip() {
+        let json = r#"{"lang": "en", "accept_pms": "whitelisted", "default_comment_sort": "qa", "some_new_pref": 3}"#;
+
+        let mut preferences: Preferences = serde_json::from_str(json).unwrap();
+        assert_eq!(preferences.accept_pms, Some(AcceptPms::Whitelisted));
+        assert_eq!(preferences.default_comment_sort, Some(CommentSort::Qa));
+
+        preferences.nightmode = Some(true);
+
+        let value = serde_json::to_value(&preferences).unwrap();
+        assert_eq!(value["nightmode"], true);
+        assert_eq!(value["some_new_pref"], 3);
+        assert_eq!(value["default_comment_sort"], "qa");
+        assert!(value.get("over_18").is_none());
+
+        // Unknown sorts are left unchanged
+        let json = json.replace(r#""qa""#, r#""hot""#);
+        let preferences: Preferences = serde_json::from_str(&json).unwrap();
+        assert_eq!(preferences.default_comment_sort, Some(CommentSort::Other));
+        let value = serde_json::to_value(&preferences).unwrap();
+        assert!(value.get("default_comment_sort").is_none());
+    }
+
+    #[test]
+    fn inbox() -> Result<()> {
+        let script_auth = ScriptAuthenticator::new(Credentials::new(
+            &env::var("REDDIT_CLIENT_ID").unwrap(),
+            &env::var("REDDIT_CLIENT_SECRET").unwrap(),
+            &env::var("REDDIT_USERNAME").unwrap(),
+            &env::var("REDDIT_PASSWORD").unwrap(),
+        ));
+        let reddit = Reddit::new(script_auth, "Windows:snew:v0.1.0 (by /u/zower98)").unwrap();
+
+        for message in reddit.inbox().all().take(5) {
+            let message = message?;
+            println!("From {:?}: {}", message.author, message.subject);
+        }
+
+        let error = reddit.inbox().compose("", "subject", "body").unwrap_err();
+        assert!(matches!(error, Error::APIError(_)));
+
+        Ok(())
+    }
+
+    #[test]
+    fn message_roundtrip() {
+        let json = serde_json::json!({
+            "id": "1a2b3c",
+            "name": "t4_1a2b3c",
+            "author": "spez",
+            "dest": "zower98",
+            "subject": "Hello",
+            "body": "Fish & chips",
+            "created_utc": 1623945600.0,
+            "new": true,
+            "was_comment": false,
+            "context": "",
+            "parent_id": null,
+            "first_message_name": "t4_1a2b3b",
+            "subreddit": null,
+            "distinguished": null
+        });
+
+        let client = AuthenticatedClient::new(OfflineAuthenticator, "snew tests").unwrap();
+        let raw = RawKind {
+            kind: String::from("t4"),
+            data: serde_json::from_value(json.clone()).unwrap(),
+        };
+        let message = Message::from_raw(raw, &client).unwrap();
+        assert_eq!(message.name.kind(), Kind::Message);
+        assert_eq!(message.first_message_name.as_ref().unwrap().id(), "1a2b3b");
+
+        // Serializes like Reddit sends it, so it can be read back
+        let value = serde_json::to_value(&message).unwrap();
+        assert_eq!(value, json);
+        assert!(serde_json::from_value::<RawMessageData>(value).is_ok());
+    }
+
+    #[test]
+    fn api_errors() {
+        assert!(check_api_errors(r#"{"json": {"errors": []}}"#).is_ok());
+        assert!(check_api_errors("{}").is_ok());
+
+        let error = check_api_errors(
+            r#"{"json": {"errors": [["USER_DOESNT_EXIST", "that user doesn't exist", "to"]]}}"#,
+        )
+        .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Reddit returned an error.\nReason:\tUSER_DOESNT_EXIST: that user doesn't exist"
+        );
+    }
+
+    #[test]
+    fn stream() -> Result<()> {
+        let application_auth = ApplicationAuthenticator::new(
+            &env::var("REDDIT_CLIENT_ID").unwrap(),
+            &env::var("REDDIT_CLIENT_SECRET").unwrap(),
+        );
+        let reddit = Reddit::new(application_auth, "Windows:snew:v0.1.0 (by /u/zower98)").unwrap();
+
+        let mut ids = HashSet::new();
+
+        for comment in reddit.subreddit("all").stream_comments().take(150) {
+            assert!(ids.insert(comment?.data.id));
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn handles_are_send_sync_static() {
+        fn assert_send_sync_static<H: Send + Sync + 'static>() {}
+
+        assert_send_sync_static::<Reddit<ScriptAuthenticator>>();
+        assert_send_sync_static::<Subreddit<ScriptAuthenticator>>();
+        assert_send_sync_static::<PostFeed<ScriptAuthenticator>>();
+        assert_send_sync_static::<Post<ScriptAuthenticator>>();
+        assert_send_sync_static::<Comment<ApplicationAuthenticator>>();
+        assert_send_sync_static::<Stream<ScriptAuthenticator, Post<ScriptAuthenticator>>>();
+        assert_send_sync_static::<InfoFeed<ScriptAuthenticator>>();
+    }
+
+    #[test]
+    fn bounded_set() {
+        let mut set = BoundedSet::new(2);
+
+        assert!(set.insert("t3_a".parse().unwrap()));
+        assert!(set.insert("t3_b".parse().unwrap()));
+        assert!(!set.insert("t3_a".parse().unwrap()));
+
+        // Forgets the oldest entry
+        assert!(set.insert("t3_c".parse().unwrap()));
+        assert!(set.insert("t3_a".parse().unwrap()));
+        assert!(!set.insert("t3_c".parse().unwrap()));
+    }
+
+    #[test]
+    fn parse_mod_log() {
+        let json = r#"{
+            "kind": "Listing",
+            "data": {
+                "after": null,
+                "before": null,
+                "children": [{
+                    "kind": "modaction",
+                    "data": {
+                        "id": "ModAction_5b0f1d3e",
+                        "action": "removelink",
+                        "mod": "AutoModerator",
+                        "mod_id36": "6l4z3",
+                        "created_utc": 1623000000.0,
+                        "subreddit": "rust",
+                        "details": "remove",
+                        "description": null,
+                        "target_author": "someone",
+                        "target_fullname": "t3_nrm0y9",
+                        "target_permalink": "/r/rust/comments/nrm0y9/title/",
+                        "target_title": "Buy cheap stuff",
+                        "target_body": null
+                    }
+                }]
+            }
+        }"#;
+
+        let listing: RawListing<RawKind<ModAction>> = serde_json::from_str(json).unwrap();
+        let action = &listing.data.children[0].data;
+
+        assert_eq!(action.moderator, "AutoModerator");
+        assert_eq!(
+            action.target_fullname.as_ref().unwrap().to_string(),
+            "t3_nrm0y9"
+        );
+        assert_eq!(action.description, None);
+    }
+
+    #[test]
+    fn parse_mod_log_ban() {
+        let json = r#"{
+            "kind": "modaction",
+            "data": {
+                "id": "ModAction_8c2e4a1f",
+                "action": "banuser",
+                "mod": "zower98",
+                "created_utc": 1623000100.5,
+                "subreddit": "rust",
+                "details": "7 days",
+                "description": "spam",
+                "target_author": "spammer",
+                "target_fullname": "t2_abc",
+                "target_permalink": null,
+                "target_title": null,
+                "target_body": null
+            }
+        }"#;
+
+        let action: RawKind<ModAction> = serde_json::from_str(json).unwrap();
+        let action = action.data;
+
+        assert_eq!(action.action, "banuser");
+        assert_eq!(action.moderator, "zower98");
+        assert_eq!(
+            action.created_utc,
+            UNIX_EPOCH + Duration::from_millis(1_623_000_100_500)
+        );
+        assert_eq!(action.details.as_deref(), Some("7 days"));
+        assert_eq!(action.target_author.as_deref(), Some("spammer"));
+        assert_eq!(action.target_permalink, None);
+    }
+
+    #[test]
+    fn ban_form_fields() {
+        let field = |form: &[(&str, String)], key: &str| {
+            form.iter()
+                .find(|(k, _)| *k == key)
+                .map(|(_, value)| value.clone())
+        };
+
+        let form = ban_form(
+            "spammer",
+            &BanOptions {
+                duration: Some(7),
+                reason: Some(String::from("Spam")),
+                ..Default::default()
+            },
+        );
+
+        assert_eq!(field(&form, "name").as_deref(), Some("spammer"));
+        assert_eq!(field(&form, "type").as_deref(), Some("banned"));
+        assert_eq!(field(&form, "duration").as_deref(), Some("7"));
+        assert_eq!(field(&form, "ban_reason").as_deref(), Some("Spam"));
+        assert_eq!(field(&form, "note"), None);
+        assert_eq!(field(&form, "ban_message"), None);
+
+        // Permanent bans leave out the duration
+        let form = ban_form("spammer", &BanOptions::default());
+        assert_eq!(field(&form, "duration"), None);
+    }
+
+    #[test]
+    fn moderator_permissions() {
+        assert_eq!(
+            permissions(&[ModPermission::Posts, ModPermission::Wiki]).unwrap(),
+            "-all,+posts,+wiki"
+        );
+        assert_eq!(
+            permissions(&[ModPermission::Posts, ModPermission::All]).unwrap(),
+            "+all"
+        );
+
+        assert!(matches!(
+            permissions(&[ModPermission::All, ModPermission::Other]),
+            Err(Error::UnknownValueError(_))
+        ));
+    }
+
+    #[test]
+    fn removal_reason_fields() {
+        let json = removal_reason_json("t3_nrm0y9", Some("1a2b3c"), Some("Rule 2"));
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(value["item_ids"], serde_json::json!(["t3_nrm0y9"]));
+        assert_eq!(value["reason_id"], "1a2b3c");
+        assert_eq!(value["mod_note"], "Rule 2");
+
+        let json = removal_reason_json("t1_h0a2b3c", None, None);
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(value["item_ids"], serde_json::json!(["t1_h0a2b3c"]));
+        assert!(value["reason_id"].is_null());
+        assert!(value["mod_note"].is_null());
+    }
+
+    #[test]
+    fn parse_moderators() {
+        let json = r#"{
+            "kind": "UserList",
+            "data": {
+                "children": [
+                    {"name": "zower98", "id": "t2_abc", "date": 1600000000.0, "rel_id": "rb_1", "mod_permissions": ["posts", "wiki", "something_new"]},
+                    {"name": "banned_user", "id": "t2_def", "date": 1600000000.0, "rel_id": "rb_2", "note": "spam", "days_left": null}
+                ],
+                "after": null,
+                "before": null
+            }
+        }"#;
+
+        let listing: RawListing<Relationship> = serde_json::from_str(json).unwrap();
+        let users = listing.data.children;
+
+        assert_eq!(
+            users[0].mod_permissions,
+            vec![
+                ModPermission::Posts,
+                ModPermission::Wiki,
+                ModPermission::Other
+            ]
+        );
+        assert_eq!(users[0].id.kind(), Kind::Account);
+        assert_eq!(users[1].note.as_deref(), Some("spam"));
+        assert_eq!(users[1].days_left, None);
+    }
+
+    #[test]
+    fn parse_modmail_conversation() {
+        let json = r#"{
+            "conversation": {
+                "id": "abc12",
+                "subject": "Why was my post removed?",
+                "state": 1,
+                "lastUpdated": "2021-06-01T12:00:00.000000+00:00",
+                "isHighlighted": false,
+                "isInternal": false,
+                "isAuto": false,
+                "numMessages": 1,
+                "owner": {"displayName": "rust", "type": "subreddit", "id": "t5_2s7lj"},
+                "participant": {},
+                "authors": [{"name": "someone", "isMod": false, "isAdmin": false, "isOp": true, "isParticipant": true, "isHidden": false, "isDeleted": false}],
+                "objIds": [{"id": "m1", "key": "messages"}, {"id": "a1", "key": "modActions"}]
+            },
+            "messages": {
+                "m1": {
+                    "id": "m1",
+                    "body": "<p>Hello</p>",
+                    "bodyMarkdown": "Hello",
+                    "author": {"name": "someone", "isMod": false, "isAdmin": false, "isOp": true, "isParticipant": true, "isHidden": false, "isDeleted": false},
+                    "date": "2021-06-01T12:00:00.000000+00:00",
+                    "isInternal": false
+                }
+            },
+            "modActions": {
+                "a1": {"id": "a1", "actionTypeId": 2, "date": "2021-06-01T13:00:00.000000+00:00", "author": {"name": "zower98", "isMod": true}}
+            }
+        }"#;
+
+        let details: RawConversationDetails = serde_json::from_str(json).unwrap();
+
+        assert_eq!(details.conversation.state, ConversationStatus::InProgress);
+        assert_eq!(ConversationStatus::from(9), ConversationStatus::Other(9));
+        assert_eq!(details.conversation.participant.unwrap().name, "");
+        assert_eq!(details.conversation.owner.display_name, "rust");
+        assert_eq!(details.messages["m1"].body_markdown, "Hello");
+        assert!(details.mod_actions["a1"].author.is_mod);
+        assert_eq!(
+            details.conversation.last_updated,
+            Some(UNIX_EPOCH + Duration::from_secs(1622548800))
+        );
+        assert_eq!(
+            details.mod_actions["a1"].date,
+            UNIX_EPOCH + Duration::from_secs(1622552400)
+        );
+    }
+
+    #[test]
+    fn parse_iso8601() {
+        let parse = |date: &str| {
+            let json = format!(
+                r#"{{"id": "a1", "actionTypeId": 2, "date": "{}", "author": {{}}}}"#,
+                date
+            );
+            serde_json::from_str::<ModmailAction>(&json).map(|action| action.date)
+        };
+
+        assert_eq!(
+            parse("2021-06-01T12:34:56.789000+00:00").unwrap(),
+            UNIX_EPOCH + Duration::from_millis(1_622_550_896_789)
+        );
+        assert_eq!(
+            parse("2021-06-01T14:34:56+02:00").unwrap(),
+            UNIX_EPOCH + Duration::from_secs(1622550896)
+        );
+        assert_eq!(
+            parse("2024-02-29T00:00:00Z").unwrap(),
+            UNIX_EPOCH + Duration::from_secs(1709164800)
+        );
+        assert_eq!(
+            parse("1969-12-31T23:00:00Z").unwrap(),
+            UNIX_EPOCH - Duration::from_secs(3600)
+        );
+
+        assert!(parse("2021-06-01").is_err());
+        assert!(parse("2021-13-01T12:00:00Z").is_err());
+        assert!(parse("2021-06-01T12:00:00").is_err());
+        assert!(parse("2021-06-01T12:00:00.+00:00").is_err());
+        assert!(parse("2021-6-01T12:00:00Z").is_err());
+    }
+
+    // A post as Reddit sends it. Tests change the fields they are about.
+    fn raw_post() -> serde_json::Value {
+        serde_json::json!({
+            "kind": "t3",
+            "data": {
+                "title": "Announcing Rust 1.53.0",
+                "ups": 1200,
+                "downs": 0,
+                "url": "https://blog.rust-lang.org/2021/06/17/Rust-1.53.0.html",
+                "author": "rustacean",
+                "subreddit": "rust",
+                "selftext": "",
+                "id": "o1xu6r",
+                "score": 1187,
+                "upvote_ratio": 0.99,
+                "num_comments": 150,
+                "created_utc": 1623945600.0,
+                "edited": 1623949200.5,
+                "permalink": "/r/rust/comments/o1xu6r/announcing_rust_1530/",
+                "domain": "blog.rust-lang.org",
+                "over_18": false,
+                "spoiler": false,
+                "stickied": true,
+                "locked": false,
+                "archived": false,
+                "is_self": false,
+                "is_video": false,
+                "distinguished": "moderator",
+                "author_fullname": null,
+                "subreddit_id": "t5_2s7lj",
+                "thumbnail": "https://b.thumbs.redditmedia.com/abc.jpg",
+                "selftext_html": null
+            }
+        })
+    }
+
+    #[test]
+    fn parse_post() {
+        let json = raw_post();
+
+        let raw: RawKind<RawPostData> = serde_json::from_value(json.clone()).unwrap();
+        let post = raw.data;
+
+        assert_eq!(post.upvote_ratio, 0.99);
+        assert_eq!(
+            post.created_utc,
+            UNIX_EPOCH + Duration::from_secs(1623945600)
+        );
+        assert_eq!(
+            post.edited,
+            Some(Edited::At(
+                UNIX_EPOCH + Duration::from_secs_f64(1623949200.5)
+            ))
+        );
+        assert_eq!(post.distinguished, Some(Distinguished::Moderator));
+        assert_eq!(post.author_fullname, None);
+        assert_eq!(post.subreddit_id.kind(), Kind::Subreddit);
+        assert_eq!(
+            post.thumbnail.as_deref(),
+            Some("https://b.thumbs.redditmedia.com/abc.jpg")
+        );
+
+        // Edited without a timestamp
+        let mut edited = json.clone();
+        edited["data"]["edited"] = serde_json::json!(true);
+        let raw: RawKind<RawPostData> = serde_json::from_value(edited).unwrap();
+        assert_eq!(raw.data.edited, Some(Edited::Unknown));
+
+        // IDs must be base 36, so the fullname can be made
+        let mut invalid = json.clone();
+        invalid["data"]["id"] = serde_json::json!("O1XU6R");
+        assert!(serde_json::from_value::<RawKind<RawPostData>>(invalid).is_err());
+
+        // Only t3 is a post
+        let mut comment = json;
+        comment["kind"] = serde_json::json!("t1");
+        let raw: RawKind<RawPostData> = serde_json::from_value(comment).unwrap();
+        assert!(PostData::try_from(raw).is_err());
+    }
+    #[test]
+    fn parse_media() {
+        let mut json = raw_post()["data"].take();
+        let media = serde_json::json!({
+            "is_gallery": true,
+            "gallery_data": {
+                "items": [
+                    {"media_id": "second", "id": 2, "caption": "The beach"},
+                    {"media_id": "first", "id": 1, "outbound_url": "https://example.com"}
+                ]
+            },
+            "media_metadata": {
+                "first": {
+                    "status": "valid",
+                    "e": "AnimatedImage",
+                    "m": "image/gif",
+                    "p": [{"y": 108, "x": 108, "u": "https://preview.redd.it/first.gif?width=108&format=png"}],
+                    "s": {"y": 500, "x": 500, "gif": "https://i.redd.it/first.gif", "mp4": "https://preview.redd.it/first.gif?format=mp4&s=abc"},
+                    "id": "first"
+                },
+                "second": {
+                    "status": "valid",
+                    "e": "Image",
+                    "m": "image/jpg",
+                    "p": [],
+                    "s": {"y": 3000, "x": 4000, "u": "https://preview.redd.it/second.jpg?width=4000&s=def"},
+                    "id": "second"
+                }
+            },
+            "preview": {
+                "images": [{
+                    "source": {"url": "https://preview.redd.it/abc.jpg?auto=webp&s=123", "width": 1920, "height": 1080},
+                    "resolutions": [{"url": "https://preview.redd.it/abc.jpg?width=108&s=456", "width": 108, "height": 60}],
+                    "variants": {},
+                    "id": "abc"
+                }],
+                "enabled": false
+            },
+            "media": null,
+            "secure_media": {
+                "reddit_video": {
+                    "bitrate_kbps": 4800,
+                    "fallback_url": "https://v.redd.it/xyz/DASH_1080.mp4?source=fallback",
+                    "height": 1080,
+                    "width": 1920,
+                    "scrubber_media_url": "https://v.redd.it/xyz/DASH_96.mp4",
+                    "dash_url": "https://v.redd.it/xyz/DASHPlaylist.mpd?a=1&v=1&f=sd",
+                    "duration": 30,
+                    "hls_url": "https://v.redd.it/xyz/HLSPlaylist.m3u8?a=1&v=1&f=sd",
+                    "is_gif": false,
+                    "transcoding_status": "completed"
+                }
+            }
+        });
+        json.as_object_mut()
+            .unwrap()
+            .extend(media.as_object().unwrap().clone());
+
+        let raw: RawPostData = serde_json::from_value(json).unwrap();
+
+        let preview = raw.preview.unwrap();
+        assert_eq!(
+            preview.images[0].source.url,
+            "https://preview.redd.it/abc.jpg?auto=webp&s=123"
+        );
+        assert_eq!(preview.images[0].resolutions[0].width, 108);
+
+        let video = raw.secure_media.unwrap().reddit_video.unwrap();
+        assert_eq!(
+            video.dash_url,
+            "https://v.redd.it/xyz/DASHPlaylist.mpd?a=1&v=1&f=sd"
+        );
+        assert_eq!(video.duration, 30);
+
+        let gallery = gallery(raw.gallery_data, raw.media_metadata);
+        assert_eq!(gallery.len(), 2);
+        assert_eq!(gallery[0].media_id, "second");
+        assert_eq!(gallery[0].caption.as_deref(), Some("The beach"));
+        assert_eq!(
+            gallery[0].source,
+            Some(Image {
+                url: String::from("https://preview.redd.it/second.jpg?width=4000&s=def"),
+                width: 4000,
+                height: 3000,
+            })
+        );
+        assert_eq!(gallery[1].mime_type.as_deref(), Some("image/gif"));
+        assert_eq!(
+            gallery[1].source.as_ref().unwrap().url,
+            "https://i.redd.it/first.gif"
+        );
+        assert_eq!(
+            gallery[1].mp4.as_deref(),
+            Some("https://preview.redd.it/first.gif?format=mp4&s=abc")
+        );
+        assert_eq!(gallery[1].resolutions.len(), 1);
+    }
+
+    #[test]
+    fn post_data_roundtrip() {
+        let mut json = raw_post();
+        json["data"]["link_flair_text"] = serde_json::json!("announcement");
+        json["data"]["link_flair_richtext"] =
+            serde_json::json!([{"e": "text", "t": "announcement"}]);
+        json["data"]["link_flair_text_color"] = serde_json::json!("dark");
+        json["data"]["preview"] = serde_json::json!({
+            "images": [{
+                "id": "abc",
+                "source": {"url": "https://example.com/a.jpg?q=1&s=1", "width": 640, "height": 480},
+                "resolutions": [],
+                "variants": {}
+            }],
+            "enabled": true
+        });
+
+        let raw: RawKind<RawPostData> = serde_json::from_value(json).unwrap();
+        let data = PostData::try_from(raw).unwrap();
+        assert_eq!(data.kind, Kind::Link);
+        assert_eq!(
+            data.link_flair.as_ref().unwrap().text.as_deref(),
+            Some("announcement")
+        );
+
+        let value = serde_json::to_value(&data).unwrap();
+        assert_eq!(value["kind"], "t3");
+        assert_eq!(value["edited"], 1623949200.5);
+        assert_eq!(value["link_flair"]["text_color"], "dark");
+        assert_eq!(
+            value["preview"]["images"][0]["source"]["url"],
+            "https://example.com/a.jpg?q=1&s=1"
+        );
+
+        let data: PostData = serde_json::from_value(value.clone()).unwrap();
+        assert_eq!(
+            data.created_utc,
+            UNIX_EPOCH + Duration::from_secs(1623945600)
+        );
+        assert_eq!(data.subreddit_id.to_string(), "t5_2s7lj");
+        assert_eq!(serde_json::to_value(&data).unwrap(), value);
+
+        // Edited can be left out
+        let mut value = value;
+        value.as_object_mut().unwrap().remove("edited");
+        let data: PostData = serde_json::from_value(value).unwrap();
+        assert_eq!(data.edited, None);
+    }
+    #[test]
+    fn comment_data_roundtrip() {
+        let json = serde_json::json!({
+            "author": "rustacean",
+            "body": "Congrats on the release!",
+            "id": "h22abc",
+            "author_flair": null,
+            "parent_id": "t3_o1xu6r",
+            "link_id": "t3_o1xu6r",
+            "score": 42,
+            "created_utc": 1623946000.0,
+            "edited": 1623949200.5,
+            "permalink": "/r/rust/comments/o1xu6r/announcing_rust_1530/h22abc/",
+            "depth": 0,
+            "is_submitter": false,
+            "distinguished": null,
+            "stickied": false,
+            "body_html": "<div class=\"md\"><p>Congrats on the release!</p>\n</div>",
+            "subreddit": "rust",
+            "controversiality": 0,
+            "total_awards_received": 0,
+            "awards": []
+        });
+
+        let data: CommentData = serde_json::from_value(json.clone()).unwrap();
+        assert_eq!(
+            data.edited,
+            Some(Edited::At(
+                UNIX_EPOCH + Duration::from_secs_f64(1623949200.5)
+            ))
+        );
+
+        assert_eq!(serde_json::to_value(&data).unwrap(), json);
+
+        // Edited without a timestamp
+        let mut json = json;
+        json["edited"] = serde_json::json!(true);
+        let data: CommentData = serde_json::from_value(json.clone()).unwrap();
+        assert_eq!(data.edited, Some(Edited::Unknown));
+        assert_eq!(serde_json::to_value(&data).unwrap(), json);
+    }
+
+    #[test]
+    fn parse_comment() {
+        let json = r#"{
+            "kind": "t1",
+            "data": {
+                "author": "rustacean",
+                "body": "Congrats on the release!",
+                "id": "h22abc",
+                "parent_id": "t1_h21xyz",
+                "link_id": "t3_o1xu6r",
+                "score": 42,
+                "created_utc": 1623946000.0,
+                "edited": false,
+                "permalink": "/r/rust/comments/o1xu6r/announcing_rust_1530/h22abc/",
+                "depth": 1,
+                "is_submitter": true,
+                "distinguished": null,
+                "stickied": false,
+                "body_html": "<div class=\"md\"><p>Congrats on the release!</p>\n</div>",
+                "subreddit": "rust",
+                "controversiality": 0,
+                "total_awards_received": 2,
+                "all_awardings": [{
+                    "id": "award_5f123e3d-4f48-42f4-9c11-e98b566d5897",
+                    "name": "Wholesome",
+                    "description": "When you come across a feel-good thing.",
+                    "count": 2,
+                    "coin_price": 150,
+                    "icon_url": "https://i.redd.it/award_images/t5_22cerq/5izbv4fn0md41_Wholesome.png",
+                    "award_type": "global"
+                }],
+                "author_flair_text": null,
+                "author_flair_richtext": []
+            }
+        }"#;
+
+        let raw: RawKind<RawCommentData> = serde_json::from_str(json).unwrap();
+        let comment = raw.data;
+
+        assert_eq!(comment.parent_id.to_string(), "t1_h21xyz");
+        assert_eq!(comment.link_id.kind(), Kind::Link);
+        assert_eq!(comment.edited, None);
+        assert_eq!(comment.depth, Some(1));
+        assert!(comment.is_submitter);
+        assert_eq!(comment.all_awardings[0].name, "Wholesome");
+        assert_eq!(comment.all_awardings[0].count, 2);
+    }
+
+    #[test]
+    fn parse_flair() {
+        let mut json = raw_post()["data"].take();
+        let flair = serde_json::json!({
+            "edited": false,
+            "thumbnail": "self",
+            "author_fullname": "t2_abc",
+            "link_flair_text": "Solved :ferris:",
+            "link_flair_css_class": "",
+            "link_flair_template_id": "7a3b",
+            "link_flair_richtext": [{"e": "text", "t": "Solved "}, {"e": "emoji", "a": ":ferris:", "u": "https://emoji.redditmedia.com/ferris.png"}],
+            "link_flair_background_color": "#ff4500",
+            "link_flair_text_color": "light",
+            "author_flair_text": null,
+            "author_flair_css_class": null,
+            "author_flair_template_id": null,
+            "author_flair_richtext": [],
+            "author_flair_background_color": null,
+            "author_flair_text_color": null
+        });
+        json.as_object_mut()
+            .unwrap()
+            .extend(flair.as_object().unwrap().clone());
+
+        let raw: RawPostData = serde_json::from_value(json).unwrap();
+        assert_eq!(raw.edited, None);
+        assert_eq!(raw.thumbnail, None);
+        assert_eq!(raw.author_fullname.unwrap().id(), "abc");
+
+        let link_flair = Option::<Flair>::from(raw.link_flair).unwrap();
+        assert_eq!(link_flair.css_class, None);
+        assert_eq!(link_flair.template_id.as_deref(), Some("7a3b"));
+        assert_eq!(
+            link_flair.richtext[1],
+            FlairRichtext::Emoji {
+                name: String::from(":ferris:"),
+                url: String::from("https://emoji.redditmedia.com/ferris.png")
+            }
+        );
+
+        assert_eq!(Option::<Flair>::from(raw.author_flair), None);
+    }
+    #[test]
+    fn parse_unknown_flair_richtext() {
+        let json = r#"{
+            "text": "Helper",
+            "css_class": null,
+            "template_id": null,
+            "richtext": [{"e": "text", "t": "Helper"}, {"e": "sparkle", "s": 3}],
+            "background_color": null,
+            "text_color": "dark"
+        }"#;
+
+        let flair: Flair = serde_json::from_str(json).unwrap();
+        assert_eq!(flair.richtext[1], FlairRichtext::Unknown);
+    }
+
+    #[test]
+    fn flair_csv() {
+        assert_eq!(csv_field(r#"Says "hi", often"#), r#""Says ""hi"", often""#);
+    }
+
+    #[test]
+    fn wiki() -> Result<()> {
+        let application_auth = ApplicationAuthenticator::new(
+            &env::var("REDDIT_CLIENT_ID").unwrap(),
+            &env::var("REDDIT_CLIENT_SECRET").unwrap(),
+        );
+        let reddit = Reddit::new(application_auth, "Windows:snew:v0.1.0 (by /u/zower98)").unwrap();
+
+        let wiki = reddit.subreddit("rust").wiki();
+        assert!(wiki.pages()?.iter().any(|page| page == "index"));
+
+        let index = wiki.page("index")?;
+        assert!(!index.content_md.is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn parse_wiki_settings() {
+        let json = r#"{"kind": "wikipagesettings", "data": {"permlevel": 1, "listed": true, "editors": [{"kind": "t2", "data": {"name": "zower98"}}]}}"#;
+
+        let raw: RawKind<RawWikiPageSettings> = serde_json::from_str(json).unwrap();
+        let settings = WikiPageSettings::from(raw.data);
+
+        assert_eq!(settings.permlevel, WikiPermLevel::ApprovedEditors);
+        assert_eq!(settings.editors, vec![String::from("zower98")]);
+
+        assert_eq!(
+            settings_form(&settings).unwrap(),
+            [("permlevel", "1"), ("listed", "true")]
+        );
+
+        // Unknown levels are kept, but not sent back
+        let unknown = r#"{"kind": "wikipagesettings", "data": {"permlevel": 7, "listed": true}}"#;
+        let raw: RawKind<RawWikiPageSettings> = serde_json::from_str(unknown).unwrap();
+        let settings = WikiPageSettings::from(raw.data);
+        assert_eq!(settings.permlevel, WikiPermLevel::Other(7));
+        assert!(matches!(
+            settings_form(&settings),
+            Err(Error::UnknownValueError(_))
+        ));
+    }
+
+    #[test]
+    fn subreddit_settings_form() {
+        let json = r#"{
+            "subreddit_id": "t5_2s7lj",
+            "title": "Rust",
+            "public_description": "All things Rust",
+            "description": "sidebar",
+            "submit_text": "",
+            "submit_link_label": null,
+            "submit_text_label": "",
+            "subreddit_type": "public",
+            "content_options": "self",
+            "language": "en",
+            "over_18": false,
+            "spoilers_enabled": false,
+            "allow_images": true,
+            "allow_videos": true,
+            "allow_polls": true,
+            "show_media": true,
+            "show_media_preview": true,
+            "collapse_deleted_comments": true,
+            "comment_score_hide_mins": 60,
+            "suggested_comment_sort": null,
+            "default_set": true,
+            "allow_discovery": true,
+            "spam_links": "high",
+            "spam_selfposts": "low",
+            "spam_comments": "low",
+            "wikimode": "modonly",
+            "wiki_edit_age": 0,
+            "wiki_edit_karma": 100,
+            "header_hover_text": "hover",
+            "crowd_control_level": 2,
+            "domain": null
+        }"#;
+
+        let mut settings: SubredditSettings = serde_json::from_str(json).unwrap();
+        settings.spoilers_enabled = true;
+
+        let form = settings.to_form().unwrap();
+        let field = |key: &str| {
+            form.iter()
+                .find(|(k, _)| k == key)
+                .map(|(_, value)| value.as_str())
+        };
+
+        assert_eq!(field("sr"), Some("t5_2s7lj"));
+        assert_eq!(field("type"), Some("public"));
+        assert_eq!(field("link_type"), Some("self"));
+        assert_eq!(field("lang"), Some("en"));
+        assert_eq!(field("allow_top"), Some("true"));
+        assert_eq!(field("spoilers_enabled"), Some("true"));
+        assert_eq!(field("comment_score_hide_mins"), Some("60"));
+        assert_eq!(field("header-title"), Some("hover"));
+        assert_eq!(field("header_hover_text"), None);
+        // Unknown settings are kept
+        assert_eq!(field("crowd_control_level"), Some("2"));
+        assert_eq!(field("domain"), None);
+        assert_eq!(field("language"), None);
+
+        // The settings still round-trip through JSON with the names Reddit uses.
+        let value = serde_json::to_value(&settings).unwrap();
+        assert_eq!(value["subreddit_type"], "public");
+        assert_eq!(value["default_set"], true);
+
+        settings.subreddit_type = SubredditType::Other;
+        assert!(settings.to_form().is_err());
+
+        let unknown = json.replace(
+            r#""content_options": "self""#,
+            r#""content_options": "media""#,
+        );
+        let settings: SubredditSettings = serde_json::from_str(&unknown).unwrap();
+        assert_eq!(settings.content_options, SubmissionType::Other);
+        assert!(settings.to_form().is_err());
+
+        let unknown = json.replace(
+            r#""suggested_comment_sort": null"#,
+            r#""suggested_comment_sort": "hot""#,
+        );
+        let settings: SubredditSettings = serde_json::from_str(&unknown).unwrap();
+        assert_eq!(settings.suggested_comment_sort, Some(CommentSort::Other));
+        assert!(matches!(
+            settings.to_form(),
+            Err(Error::UnknownValueError(_))
+        ));
+    }
+
+    #[test]
+    fn subreddit_rules() {
+        let json = r#"{
+            "kind": "link",
+            "description": "Memes belong in r/mysubredditmemes",
+            "short_name": "No memes",
+            "violation_reason": "Meme",
+            "created_utc": 1609459200.0,
+            "priority": 1,
+            "description_html": "<p>Memes belong in r/mysubredditmemes</p>"
+        }"#;
+
+        let rule: Rule = serde_json::from_str(json).unwrap();
+        assert_eq!(rule.kind, RuleKind::Link);
+        assert_eq!(rule.short_name, "No memes");
+        assert_eq!(rule.violation_reason.as_deref(), Some("Meme"));
+        assert_eq!(rule.priority, 1);
+
+        let unknown = json.replace(r#""kind": "link""#, r#""kind": "something_new""#);
+        let rule: Rule = serde_json::from_str(&unknown).unwrap();
+        assert_eq!(rule.kind, RuleKind::Other);
+    }
+
+    #[test]
+    fn invalid_timestamps() {
+        #[derive(Debug, serde::Deserialize)]
+        struct Timestamped {
+            #[serde(with = "crate::things::raw::timestamp")]
+            #[allow(dead_code)]
+            time: std::time::SystemTime,
+        }
+
+        let parse =
+            |time: &str| serde_json::from_str::<Timestamped>(&format!(r#"{{"time": {}}}"#, time));
+
+        assert!(parse("1609459200.5").is_ok());
+        // An error rather than a panic
+        assert!(parse("-1.0").is_err());
+        assert!(parse("1e300").is_err());
+    }
+
+    #[test]
+    fn post_requirements_check() {
+        let json = r#"{
+            "title_regexes": [],
+            "body_blacklisted_strings": ["spam"],
+            "title_blacklisted_strings": ["[META]"],
+            "body_text_max_length": 20,
+            "title_required_strings": ["[Question]", "[Discussion]"],
+            "guidelines_text": null,
+            "gallery_min_items": null,
+            "domain_blacklist": [],
+            "domain_whitelist": [],
+            "title_text_max_length": 300,
+            "body_restriction_policy": "required",
+            "link_restriction_policy": "none",
+            "guidelines_display_policy": null,
+            "body_required_strings": [],
+            "title_text_min_length": 15,
+            "gallery_captions_requirement": "none",
+            "is_flair_required": true,
+            "gallery_max_items": null,
+            "gallery_urls_requirement": "none",
+            "body_regexes": [],
+            "link_repost_age": null,
+            "body_text_min_length": null
+        }"#;
+
+        let requirements: PostRequirements = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            requirements.body_restriction_policy,
+            Some(BodyRestriction::Required)
+        );
+        assert!(requirements.is_flair_required);
+        assert_eq!(
+            serde_json::from_str::<BodyRestriction>(r#""somethingNew""#).unwrap(),
+            BodyRestriction::Other
+        );
+
+        assert!(requirements
+            .check("[question] How do I do this?", "Some text")
+            .is_empty());
+        assert_eq!(
+            requirements.check("[meta] Hi", ""),
+            vec![
+                Violation::TitleTooShort,
+                Violation::TitleMissingRequiredString,
+                Violation::TitleContains(String::from("[META]")),
+                Violation::BodyRequired,
+            ]
+        );
+        assert_eq!(
+            requirements.check("[Discussion] Spam thread", "This is spam, lots of it"),
+            vec![
+                Violation::BodyTooLong,
+                Violation::BodyContains(String::from("spam")),
+            ]
+        );
+    }
+
+    #[test]
+    fn multireddit() {
+        let json = r#"[{
+            "kind": "LabeledMulti",
+            "data": {
+                "can_edit": true,
+                "display_name": "Languages",
+                "name": "languages",
+                "description_html": "",
+                "num_subscribers": 3,
+                "copied_from": null,
+                "icon_url": "",
+                "subreddits": [{"name": "rust"}, {"name": "golang"}],
+                "created_utc": 1609459200.0,
+                "visibility": "public",
+                "created": 1609459200.0,
+                "over_18": false,
+                "path": "/user/spez/m/languages/",
+                "owner": "spez",
+                "key_color": null,
+                "is_subscriber": false,
+                "owner_id": "t2_1w72",
+                "description_md": "Programming languages",
+                "is_favorited": false
+            }
+        }]"#;
+
+        let raw: Vec<RawKind<RawMultireddit>> = serde_json::from_str(json).unwrap();
+        let multi: MultiredditInfo = raw.into_iter().next().unwrap().data.into();
+        assert_eq!(multi.display_name, "Languages");
+        assert_eq!(multi.subreddits, vec!["rust", "golang"]);
+        assert_eq!(multi.visibility, Visibility::Public);
+        assert_eq!(
+            serde_json::from_str::<Visibility>(r#""friends""#).unwrap(),
+            Visibility::Other
+        );
+        assert_eq!(multi.icon_url, None);
+
+        let options = MultiredditOptions {
+            subreddits: vec![String::from("rust")],
+            ..Default::default()
+        };
+        assert_eq!(
+            serde_json::to_value(&options).unwrap(),
+            serde_json::json!({
+                "description_md": "",
+                "subreddits": [{"name": "rust"}],
+                "visibility": "private"
+            })
+        );
+    }
+
     #[test]
     #[should_panic]
     fn unauthorized_anonoymous() {
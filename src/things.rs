@@ -1,61 +1,110 @@
 //! Reddit 'things'. In the API, a thing is a type + fullname.
-use serde::Deserialize;
+use std::{
+    cmp::Ordering,
+    convert::TryFrom,
+    fmt,
+    hash::{Hash, Hasher},
+    str::FromStr,
+    time::SystemTime,
+};
+
+use serde::{de::DeserializeOwned, Deserialize, Serialize, Serializer};
 
 use self::raw::{
     comment::RawCommentData, generic_kind::RawKind, listing::RawListing, post::RawPostData,
+    RawInfo, RawThing,
 };
 use crate::{
     auth::{AuthenticatedClient, Authenticator},
-    reddit::Result,
+    flair::{Flair, SubredditFlair},
+    media::{self, GalleryItem, Media, Preview},
+    moderation::{CommentModeration, PostModeration, SubredditModeration},
+    multireddit::{self, Multireddit, MultiredditInfo},
+    reddit::{check_api_errors, Error, Reddit, Result},
+    rules::{PostRequirements, RemovalReasons, SubredditRules},
+    settings::SubredditSettings,
+    stream::Stream,
+    wiki::SubredditWiki,
 };
 
 /// A handle to interact with a subreddit.
 /// See [`PostFeed`] for some gotchas when iterating over Posts.
 #[derive(Debug)]
-pub struct Subreddit<'a, T: Authenticator> {
+pub struct Subreddit<T: Authenticator> {
     pub name: String,
     pub url: String,
-    pub(crate) client: &'a AuthenticatedClient<T>,
+    // Extra parameters for all feeds, such as the geo filter of r/popular.
+    pub(crate) params: Params,
+    pub(crate) client: AuthenticatedClient<T>,
 }
 
-impl<'a, T: Authenticator> Subreddit<'a, T> {
+impl<T: Authenticator> Subreddit<T> {
     /// Create a instance of a subreddit
     /// Use [`crate::reddit::Reddit::subreddit()`] instead.
-    pub fn create(name: &str, client: &'a AuthenticatedClient<T>) -> Self {
+    pub fn create(name: &str, client: &AuthenticatedClient<T>) -> Self {
         Self {
             name: String::from(name),
             url: format!("{}r/{}", crate::reddit::URL, name),
-            client,
+            params: Params::default(),
+            client: client.clone(),
+        }
+    }
+
+    /// Leave out posts from these subreddits.
+    /// Reddit only supports this for [`crate::reddit::Reddit::all`] and [`crate::reddit::Reddit::popular`].
+    /// ```no_run
+    /// # fn main() -> snew::reddit::Result<()> {
+    /// # use snew::{reddit::Reddit, auth::{ApplicationAuthenticator}};
+    /// # let reddit = Reddit::new(
+    /// #    ApplicationAuthenticator::new("client_id", "client_secret"),
+    /// #    "<Operating system>:snew:v0.1.0 (by u/<reddit username>)"
+    /// #    ).unwrap();
+    /// for post in reddit.all().exclude(&["funny", "pics"]).hot().take(10) {
+    ///     println!("{}", post?.data.title);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn exclude(mut self, subreddits: &[&str]) -> Self {
+        for subreddit in subreddits {
+            self.name.push('-');
+            self.name.push_str(subreddit);
         }
+        self.url = format!("{}r/{}", crate::reddit::URL, self.name);
+        self
     }
 
-    pub fn hot(&self) -> PostFeed<'a, T> {
+    pub fn hot(&self) -> PostFeed<T> {
         self.posts_sorted("hot")
     }
 
     // new() is usually reserved for creating a instance of the struct
     // Inconsistent to put new_sorting, and much easier to use this way than to use x_sorting for all the functions
-    #[allow(clippy::clippy::new_ret_no_self)]
-    pub fn new(&self) -> PostFeed<'a, T> {
+    #[allow(clippy::new_ret_no_self)]
+    pub fn new(&self) -> PostFeed<T> {
         self.posts_sorted("new")
     }
 
-    pub fn random(&self) -> PostFeed<'a, T> {
+    pub fn random(&self) -> PostFeed<T> {
         self.posts_sorted("random")
     }
 
-    pub fn rising(&self) -> PostFeed<'a, T> {
+    pub fn rising(&self) -> PostFeed<T> {
         self.posts_sorted("rising")
     }
 
-    pub fn top(&self) -> PostFeed<'a, T> {
+    pub fn top(&self) -> PostFeed<T> {
         self.posts_sorted("top")
     }
 
-    pub fn best(&self) -> PostFeed<'a, T> {
+    pub fn best(&self) -> PostFeed<T> {
         self.posts_sorted("best")
     }
 
+    pub fn controversial(&self) -> PostFeed<T> {
+        self.posts_sorted("controversial")
+    }
+
     // /// Submit a text post.
     // pub fn submit(&self, title: &str, text: &str) -> Post<T> {
     //     self.client.get(
@@ -65,21 +114,220 @@ impl<'a, T: Authenticator> Subreddit<'a, T> {
     //     todo!()
     // }
 
-    fn posts_sorted(&self, path: &str) -> PostFeed<'a, T> {
-        PostFeed {
-            limit: 100,
-            url: format!("{}/{}", self.url, path),
-            cached_posts: Vec::new(),
-            client: self.client,
-            after: String::from(""),
+    /// Search for posts in this subreddit.
+    /// The search is restricted to this subreddit by default, see [`PostFeed::restrict_sr`].
+    /// ```no_run
+    /// # fn main() -> snew::reddit::Result<()> {
+    /// # use snew::{reddit::Reddit, auth::{ApplicationAuthenticator}};
+    /// # let reddit = Reddit::new(
+    /// #    ApplicationAuthenticator::new("client_id", "client_secret"),
+    /// #    "<Operating system>:snew:v0.1.0 (by u/<reddit username>)"
+    /// #    ).unwrap();
+    /// use snew::things::{SearchSort, TimeFilter};
+    ///
+    /// let results = reddit
+    ///     .subreddit("rust")
+    ///     .search("borrow checker")
+    ///     .sort(SearchSort::Top)
+    ///     .time(TimeFilter::Month);
+    ///
+    /// for post in results.take(10) {
+    ///     println!("{}", post?.data.title);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn search(&self, query: &str) -> PostFeed<T> {
+        self.posts_sorted("search")
+            .param("q", query)
+            .restrict_sr(true)
+    }
+
+    /// Subscribe the logged in user to this subreddit.
+    pub fn subscribe(&self) -> Result<()> {
+        self.set_subscription("sub")
+    }
+
+    /// Unsubscribe the logged in user from this subreddit.
+    pub fn unsubscribe(&self) -> Result<()> {
+        self.set_subscription("unsub")
+    }
+
+    fn set_subscription(&self, action: &str) -> Result<()> {
+        self.client.ensure_user()?;
+
+        self.client.post(
+            &format!("{}api/subscribe", crate::reddit::URL),
+            &[
+                ("action", action),
+                ("sr_name", &self.name),
+                ("skip_initial_defaults", "true"),
+            ],
+        )?;
+
+        Ok(())
+    }
+
+    /// Get metadata about this subreddit, such as the description and amount of subscribers.
+    pub fn about(&self) -> Result<SubredditInfo> {
+        let text = self
+            .client
+            .get(&format!("{}/about", self.url), None::<&()>)?
+            .text()?;
+
+        let raw: RawKind<SubredditInfo> = serde_json::from_str(&text)?;
+
+        Ok(raw.data)
+    }
+
+    /// Accept an invite to moderate this subreddit. See [`SubredditModeration::invite_moderator`].
+    pub fn accept_moderator_invite(&self) -> Result<()> {
+        self.client.ensure_user()?;
+
+        let text = self
+            .client
+            .post(
+                &format!("{}/api/accept_moderator_invite", self.url),
+                &[("api_type", "json")],
+            )?
+            .text()?;
+
+        check_api_errors(&text)
+    }
+
+    /// The settings of this subreddit. Only available if the logged in user moderates this subreddit.
+    pub fn settings(&self) -> Result<SubredditSettings> {
+        self.client.ensure_user()?;
+
+        let text = self
+            .client
+            .get(&format!("{}/about/edit", self.url), None::<&()>)?
+            .text()?;
+
+        let raw: RawKind<SubredditSettings> = serde_json::from_str(&text)?;
+
+        Ok(raw.data)
+    }
+
+    /// Replace the settings of this subreddit. Use [`Self::settings`] to get the current settings, then modify them.
+    /// Settings not known to snew are kept in [`SubredditSettings::other`], and are sent back unchanged.
+    /// ```no_run
+    /// # fn main() -> snew::reddit::Result<()> {
+    /// # use snew::{reddit::Reddit, auth::{ScriptAuthenticator, Credentials}};
+    /// # let reddit = Reddit::new(
+    /// #    ScriptAuthenticator::new(Credentials::new("client_id", "client_secret", "username", "password")),
+    /// #    "<Operating system>:snew:v0.1.0 (by u/<reddit username>)"
+    /// #    ).unwrap();
+    /// let subreddit = reddit.subreddit("mysubreddit");
+    ///
+    /// let mut settings = subreddit.settings()?;
+    /// settings.public_description = String::from("A place for all things related to my subreddit");
+    /// settings.spoilers_enabled = true;
+    ///
+    /// subreddit.update_settings(&settings)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn update_settings(&self, settings: &SubredditSettings) -> Result<()> {
+        self.client.ensure_user()?;
+
+        let mut form = settings.to_form()?;
+        form.push((String::from("api_type"), String::from("json")));
+
+        let text = self
+            .client
+            .post(&format!("{}api/site_admin", crate::reddit::URL), &form)?
+            .text()?;
+
+        check_api_errors(&text)
+    }
+
+    /// The rules of this subreddit.
+    pub fn rules(&self) -> SubredditRules<T> {
+        SubredditRules::create(&self.name, &self.client)
+    }
+
+    /// The removal reasons of this subreddit, used when removing posts and comments.
+    /// Only available if the logged in user moderates this subreddit.
+    pub fn removal_reasons(&self) -> RemovalReasons<T> {
+        RemovalReasons::create(&self.name, &self.client)
+    }
+
+    /// The requirements posts in this subreddit must meet, such as the length of the title.
+    /// See [`PostRequirements::check`] to validate a post before submitting it.
+    pub fn post_requirements(&self) -> Result<PostRequirements> {
+        let text = self
+            .client
+            .get(
+                &format!(
+                    "{}api/v1/{}/post_requirements",
+                    crate::reddit::URL,
+                    self.name
+                ),
+                None::<&()>,
+            )?
+            .text()?;
+
+        Ok(serde_json::from_str(&text)?)
+    }
+
+    /// The wiki of this subreddit.
+    pub fn wiki(&self) -> SubredditWiki<T> {
+        SubredditWiki::create(&self.url, &self.client)
+    }
+
+    /// Flair templates of this subreddit, and the flair of its users and posts.
+    pub fn flair(&self) -> SubredditFlair<T> {
+        SubredditFlair::create(&self.url, &self.client)
+    }
+
+    /// Moderation queues, the moderation log and the user lists of this subreddit. Only available if the logged in user moderates this subreddit.
+    pub fn mod_(&self) -> SubredditModeration<T> {
+        SubredditModeration::create(&self.url, &self.client)
+    }
+
+    /// New posts in this subreddit, as they are submitted. See [`Stream`].
+    pub fn stream_submissions(&self) -> Stream<T, Post<T>> {
+        Stream::new(format!("{}/new", self.url), &self.client).with_params(&self.params)
+    }
+
+    /// New comments in this subreddit, as they are made. See [`Stream`].
+    pub fn stream_comments(&self) -> Stream<T, Comment<T>> {
+        Stream::new(format!("{}/comments", self.url), &self.client).with_params(&self.params)
+    }
+
+    fn posts_sorted(&self, path: &str) -> PostFeed<T> {
+        Feed::new(format!("{}/{}", self.url, path), &self.client).with_params(&self.params)
+    }
+}
+
+/// A post. Its fields are in [`Self::data`].
+/// Serializes as its [`PostData`].
+#[derive(Debug)]
+pub struct Post<T: Authenticator> {
+    client: AuthenticatedClient<T>,
+    pub data: PostData,
+}
+
+impl<T: Authenticator> Clone for Post<T> {
+    fn clone(&self) -> Self {
+        Self {
+            client: self.client.clone(),
+            data: self.data.clone(),
         }
     }
 }
 
-/// A post.
-#[derive(Debug, Clone)]
-pub struct Post<'a, T: Authenticator> {
-    client: &'a AuthenticatedClient<T>,
+impl<T: Authenticator> Serialize for Post<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        self.data.serialize(serializer)
+    }
+}
+
+/// The data of a [`Post`], without the client, so it can be stored or sent to other threads.
+/// Use [`Self::attach`] to interact with the post again.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PostData {
     pub title: String,
     /// Upvotes.
     pub ups: i32,
@@ -94,275 +342,2153 @@ pub struct Post<'a, T: Authenticator> {
     /// The subreddit this post belongs to
     pub subreddit: String,
     /// The unique base 36 ID of this post
+    #[serde(deserialize_with = "raw::base36")]
     pub id: String,
-    /// The 'kind'. This should always be t3. Combine with [`Self::id`] to get the fullname of this post.
-    pub kind: String,
+    /// The kind, always [`Kind::Link`]. See [`Post::fullname`].
+    pub kind: Kind,
+    /// The flair of this post, if any.
+    pub link_flair: Option<Flair>,
+    /// The flair of the author in this subreddit, if any.
+    pub author_flair: Option<Flair>,
+    /// Upvotes minus downvotes, as shown on Reddit.
+    pub score: i32,
+    /// The fraction of votes that are upvotes, between 0 and 1.
+    pub upvote_ratio: f64,
+    pub num_comments: i32,
+    #[serde(with = "raw::timestamp")]
+    pub created_utc: SystemTime,
+    /// When this post was last edited, if ever.
+    #[serde(default, deserialize_with = "raw::edited")]
+    pub edited: Option<Edited>,
+    /// The path of the comment section, e.g. `/r/rust/comments/abc123/title/`.
+    pub permalink: String,
+    /// The domain of [`Self::url`], or e.g. `self.rust` for text posts.
+    pub domain: String,
+    /// Not safe for work.
+    pub over_18: bool,
+    pub spoiler: bool,
+    /// Pinned to the top of the subreddit.
+    pub stickied: bool,
+    /// New comments are not allowed.
+    pub locked: bool,
+    /// Too old to vote or comment on.
+    pub archived: bool,
+    /// A text post, rather than a link.
+    pub is_self: bool,
+    /// Hosted on Reddit's own video host.
+    pub is_video: bool,
+    /// Whether the author has marked this post as a moderator or admin.
+    pub distinguished: Option<Distinguished>,
+    /// The fullname of the author. None if the author has deleted their account.
+    pub author_fullname: Option<Fullname>,
+    pub subreddit_id: Fullname,
+    /// The URL of the thumbnail image, if any.
+    pub thumbnail: Option<String>,
+    /// The text of this post, rendered to HTML. None for link posts.
+    pub selftext_html: Option<String>,
+    /// Preview images of the linked image, video or article, if any.
+    pub preview: Option<Preview>,
+    /// A video or embed, if any. Prefer [`Self::secure_media`], which has https URLs.
+    pub media: Option<Media>,
+    pub secure_media: Option<Media>,
+    /// The images of a gallery post, in order. Empty for other posts.
+    pub gallery: Vec<GalleryItem>,
 }
 
-impl<'a, T: Authenticator> Post<'a, T> {
+impl PostData {
+    /// Attach the client of `reddit`, to interact with this post again.
+    /// ```no_run
+    /// # fn main() -> snew::reddit::Result<()> {
+    /// # use snew::{reddit::Reddit, auth::{ApplicationAuthenticator}};
+    /// # let reddit = Reddit::new(
+    /// #    ApplicationAuthenticator::new("client_id", "client_secret"),
+    /// #    "<Operating system>:snew:v0.1.0 (by u/<reddit username>)"
+    /// #    ).unwrap();
+    /// use snew::things::PostData;
+    ///
+    /// let post = reddit.subreddit("rust").hot().next().unwrap()?;
+    /// let json = serde_json::to_string(&post)?;
+    ///
+    /// let data: PostData = serde_json::from_str(&json)?;
+    /// for comment in data.attach(&reddit).comments().take(5) {
+    ///     println!("{}", comment?.data.body);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn attach<T: Authenticator>(self, reddit: &Reddit<T>) -> Post<T> {
+        Post {
+            client: reddit.client().clone(),
+            data: self,
+        }
+    }
+}
+
+impl<T: Authenticator> Post<T> {
+    /// Detach the data of this post from the client.
+    pub fn into_data(self) -> PostData {
+        self.data
+    }
+
     /// Get the comments for this post.
     /// Currently these are only the top level comments.
-    pub fn comments(&self) -> CommentFeed<'a, T> {
+    pub fn comments(&self) -> CommentFeed<T> {
         CommentFeed {
-            client: self.client,
+            client: self.client.clone(),
             url: format!(
                 "{}r/{}/comments/{}",
                 crate::reddit::URL,
-                self.subreddit,
-                self.id
+                self.data.subreddit,
+                self.data.id
             ),
             cached_comments: Vec::new(),
         }
     }
+
+    /// The URLs of the media of this post, in the best available resolution: every image of a gallery,
+    /// the video hosted by Reddit (without audio, see [`crate::media::RedditVideo`]), the linked image, or else the preview image, preferring the animated versions of animated previews.
+    /// Empty for text posts and links without a preview.
+    pub fn media_urls(&self) -> Vec<String> {
+        if !self.data.gallery.is_empty() {
+            return self
+                .data
+                .gallery
+                .iter()
+                .filter_map(|item| {
+                    item.mp4
+                        .clone()
+                        .or_else(|| Some(item.source.as_ref()?.url.clone()))
+                })
+                .collect();
+        }
+
+        let video = self
+            .data
+            .secure_media
+            .iter()
+            .chain(&self.data.media)
+            .find_map(|media| media.reddit_video.as_ref());
+        if let Some(video) = video {
+            return vec![video.fallback_url.clone()];
+        }
+
+        if self.data.is_self {
+            return Vec::new();
+        }
+
+        if self.data.domain == "i.redd.it" {
+            return vec![self.data.url.clone()];
+        }
+
+        // Animated previews have the animation as variants, and a still image as the source.
+        self.data
+            .preview
+            .iter()
+            .flat_map(|preview| &preview.images)
+            .map(|image| {
+                let variant = image
+                    .variants
+                    .get("mp4")
+                    .or_else(|| image.variants.get("gif"));
+
+                variant
+                    .map_or(&image.source, |variant| &variant.source)
+                    .url
+                    .clone()
+            })
+            .take(1)
+            .collect()
+    }
+
+    /// Download the media of this post, in the order of [`Self::media_urls`].
+    /// ```no_run
+    /// # fn main() -> snew::reddit::Result<()> {
+    /// # use snew::{reddit::Reddit, auth::{ApplicationAuthenticator}};
+    /// # let reddit = Reddit::new(
+    /// #    ApplicationAuthenticator::new("client_id", "client_secret"),
+    /// #    "<Operating system>:snew:v0.1.0 (by u/<reddit username>)"
+    /// #    ).unwrap();
+    /// for post in reddit.subreddit("pics").top().take(5) {
+    ///     let post = post?;
+    ///     for (i, media) in post.download_media()?.into_iter().enumerate() {
+    ///         std::fs::write(format!("{}-{}", post.data.id, i), media).unwrap();
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn download_media(&self) -> Result<Vec<Vec<u8>>> {
+        self.media_urls()
+            .iter()
+            .map(|url| self.client.download(url))
+            .collect()
+    }
+
+    /// The fullname of this post, e.g. `t3_abc123`.
+    ///
+    /// # Panics
+    /// If [`PostData::id`] was changed to something that is not base 36. IDs from Reddit are validated.
+    pub fn fullname(&self) -> Fullname {
+        Fullname::new(Kind::Link, &self.data.id).expect("Invalid post ID")
+    }
+
+    /// Moderate this post. Only available if the logged in user moderates the subreddit.
+    pub fn mod_(&self) -> PostModeration<T> {
+        PostModeration::create(self.fullname(), &self.client)
+    }
 }
 
-/// Represents interacting with a set of posts, meant to be iterated over. As long as there are posts to iterate over, this iterator will continue. You may wish to take() some elements.
-/// The iterator returns a Result<Post, Error>. The errors are either from the HTTP request or the JSON parsing.
+/// Represents interacting with a set of things from a Reddit listing, meant to be iterated over. As long as there are things to iterate over, this iterator will continue. You may wish to take() some elements.
+/// The iterator returns a Result<I, Error>. The errors are either from the HTTP request or the JSON parsing.
 #[derive(Debug)]
-pub struct PostFeed<'a, T: Authenticator> {
-    /// The amount of posts to request from the Reddit API. This does not mean you can only iterate over this many posts.
+pub struct Feed<T: Authenticator, I> {
+    /// The amount of things to request from the Reddit API. This does not mean you can only iterate over this many things.
     /// The Iterator will simply make more requests if you iterate over more than this limit.
     /// You should set this to a specific number if you know that you will be making some exact number of requests < 100, so
-    /// the iterator doesnt fetch more posts than it needs to. If you dont know how many you are iterating over, just leave it at the default
+    /// the iterator doesnt fetch more things than it needs to. If you dont know how many you are iterating over, just leave it at the default
     /// which is 100, the max Reddit allows.
     pub limit: i32,
     url: String,
-    cached_posts: Vec<Post<'a, T>>,
-    client: &'a AuthenticatedClient<T>,
+    params: Params,
+    cached: Vec<I>,
+    client: AuthenticatedClient<T>,
     after: String,
+    exhausted: bool,
 }
 
-impl<'a, T: Authenticator> Iterator for PostFeed<'a, T> {
-    type Item = Result<Post<'a, T>>;
-
-    fn next(&mut self) -> Option<Self::Item> {
-        self.cached_posts.pop().map(Ok).or_else_transpose(|| {
-            let text = self
-                .client
-                .get(
-                    &self.url,
-                    Some(&[
-                        ("limit", self.limit.to_string()),
-                        ("after", self.after.clone()),
-                    ]),
-                )?
-                .text()?;
-
-            let listing: RawListing<RawKind<RawPostData>> = serde_json::from_str(&text)?;
-
-            // Make sure the next HTTP request gets posts after the last one we fetched.
-            if let Some(after) = listing.data.pagination.after {
-                self.after = after;
-            }
+/// A set of posts, meant to be iterated over. See [`Feed`].
+pub type PostFeed<T> = Feed<T, Post<T>>;
 
-            let client = self.client;
+// Extra query parameters of a feed or stream, with at most one value per key.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Params(Vec<(&'static str, String)>);
 
-            // Add posts to the cached_posts array, converting from RawPost to Post in the process
-            self.cached_posts.extend(
-                listing
-                    .data
-                    .children
-                    .into_iter()
-                    .rev()
-                    .map(|raw| (raw, client))
-                    .map(From::from),
-            );
-            Ok(self.cached_posts.pop())
-        })
+impl Params {
+    // Set a parameter, replacing any earlier value for the same key.
+    pub(crate) fn set(&mut self, key: &'static str, value: &str) {
+        self.0.retain(|(k, _)| *k != key);
+        self.0.push((key, String::from(value)));
     }
 }
 
-/// A comment.
-#[derive(Debug)]
-pub struct Comment {
-    pub author: String,
-    pub body: String,
-    pub id: String,
+impl<T: Authenticator, I> Feed<T, I> {
+    pub(crate) fn new(url: String, client: &AuthenticatedClient<T>) -> Self {
+        Self {
+            limit: 100,
+            url,
+            params: Params::default(),
+            cached: Vec::new(),
+            client: client.clone(),
+            after: String::from(""),
+            exhausted: false,
+        }
+    }
+
+    // Set a query parameter sent with every request, replacing any earlier value for the same key.
+    pub(crate) fn param(mut self, key: &'static str, value: &str) -> Self {
+        self.params.set(key, value);
+        self
+    }
+
+    // Set all the given query parameters, e.g. those of a subreddit or stream.
+    pub(crate) fn with_params(mut self, params: &Params) -> Self {
+        for (key, value) in &params.0 {
+            self.params.set(key, value);
+        }
+        self
+    }
+
+    /// Only include things from the given time period.
+    /// Reddit only respects this for top and controversial sorting, searches and user listings.
+    pub fn time(self, time: TimeFilter) -> Self {
+        self.param("t", time.as_str())
+    }
+
+    /// How the things are sorted. Only applies to feeds that are not already sorted by their URL,
+    /// i.e. searches ([`SearchSort`]) and user listings ([`UserSort`]). Other feeds, such as [`Subreddit::hot`], ignore it.
+    pub fn sort<S: Sorting>(self, sort: S) -> Self {
+        self.param("sort", sort.as_str())
+    }
 }
 
-/// A set of comments, meant to be iterated over.
-#[derive(Debug)]
-pub struct CommentFeed<'a, T: Authenticator> {
-    url: String,
-    client: &'a AuthenticatedClient<T>,
-    cached_comments: Vec<Comment>,
+impl<T: Authenticator> PostFeed<T> {
+    /// Whether a search from a subreddit only includes posts from that subreddit.
+    /// Only applies to searches, see [`Subreddit::search`]. Other feeds ignore it.
+    pub fn restrict_sr(self, restrict: bool) -> Self {
+        self.param("restrict_sr", if restrict { "true" } else { "false" })
+    }
+
+    /// The query syntax used for the search. Only applies to searches, other feeds ignore it.
+    pub fn syntax(self, syntax: SearchSyntax) -> Self {
+        self.param("syntax", syntax.as_str())
+    }
 }
-impl<'a, T: Authenticator> Iterator for CommentFeed<'a, T> {
-    type Item = Result<Comment>;
+
+impl<T: Authenticator, I: FromListing<T>> Iterator for Feed<T, I> {
+    type Item = Result<I>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        self.cached_comments.pop().map(Ok).or_else_transpose(|| {
-            let text = self.client.get(&self.url, None::<&()>)?.text()?;
+        if self.cached.is_empty() && self.exhausted {
+            return None;
+        }
 
-            // The first listing returned by reddit is the post the comments belong to (smh..), the second listing are the comments.
-            // So we just toss away all the json from the first element of the tuple.
-            let listings: (Empty, RawListing<RawKind<RawCommentData>>) =
-                serde_json::from_str(&text)?;
+        self.cached.pop().map(Ok).or_else_transpose(|| {
+            let mut queries = vec![
+                ("limit", self.limit.to_string()),
+                ("after", self.after.clone()),
+            ];
+            queries.extend(self.params.0.iter().cloned());
 
-            // Add comments to the cached_commments array, converting from RawComment to Comment in the process
-            self.cached_comments
-                .extend(listings.1.data.children.into_iter().rev().map(From::from));
+            let text = self.client.get(&self.url, Some(&queries))?.text()?;
 
-            Ok(self.cached_comments.pop())
+            let listing: RawListing<I::Raw> = serde_json::from_str(&text)?;
+
+            let client = &self.client;
+
+            // Convert from the raw data first, so the page is requested again if that fails
+            let things = listing
+                .data
+                .children
+                .into_iter()
+                .rev()
+                .map(|raw| I::from_raw(raw, client))
+                .collect::<Result<Vec<_>>>()?;
+
+            // Make sure the next HTTP request gets things after the last one we fetched.
+            // No after means this was the last page.
+            match listing.data.pagination.after {
+                Some(after) => self.after = after,
+                None => self.exhausted = true,
+            }
+
+            self.cached.extend(things);
+            Ok(self.cached.pop())
         })
     }
 }
 
-/// Information about the authenticated user
-#[derive(Debug, Deserialize)]
-pub struct Me {
-    pub name: String,
-    pub total_karma: i32,
-    pub link_karma: i32,
-    pub comment_karma: i32,
-    pub verified: bool,
+/// Something that can be an entry in a Reddit listing, and thus be iterated over with a [`Feed`].
+pub trait FromListing<T: Authenticator>: Sized {
+    /// The raw JSON representation of one child of the listing.
+    #[doc(hidden)]
+    type Raw: DeserializeOwned;
+
+    #[doc(hidden)]
+    fn from_raw(raw: Self::Raw, client: &AuthenticatedClient<T>) -> Result<Self>;
 }
 
-pub trait Transpose<T> {
-    fn or_else_transpose<F: FnOnce() -> Result<Option<T>>>(self, f: F) -> Option<Result<T>>;
+impl<T: Authenticator> FromListing<T> for Post<T> {
+    type Raw = RawKind<RawPostData>;
+
+    fn from_raw(raw: Self::Raw, client: &AuthenticatedClient<T>) -> Result<Self> {
+        Self::try_from((raw, client))
+    }
 }
 
-impl<T> Transpose<T> for Option<Result<T>> {
-    fn or_else_transpose<F: FnOnce() -> Result<Option<T>>>(self, f: F) -> Option<Result<T>> {
-        if self.is_none() {
-            f().transpose()
-        } else {
-            self
+/// The time period to include things from. See [`Feed::time`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimeFilter {
+    Hour,
+    Day,
+    Week,
+    Month,
+    Year,
+    All,
+}
+
+impl TimeFilter {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Self::Hour => "hour",
+            Self::Day => "day",
+            Self::Week => "week",
+            Self::Month => "month",
+            Self::Year => "year",
+            Self::All => "all",
         }
     }
 }
 
-// fn or_else_transpose<T, F>(opt: Option<Result<T>>, f: F) -> Option<Result<T>>
-// where
-//     F: FnOnce() -> Result<Option<T>>,
-// {
-//     if let None = opt {
-//         f().transpose()
-//     } else {
-//         opt
-//     }
-// }
+/// The order of search results. See [`Feed::sort`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SearchSort {
+    Relevance,
+    Hot,
+    Top,
+    New,
+    /// Most comments first.
+    Comments,
+}
 
-// Create a post from som raw data.
-impl<'a, T: Authenticator> From<(RawKind<RawPostData>, &'a AuthenticatedClient<T>)>
-    for Post<'a, T>
-{
-    fn from(raw: (RawKind<RawPostData>, &'a AuthenticatedClient<T>)) -> Self {
-        let (raw, client) = raw;
-        Self {
-            client,
-            title: raw.data.title,
-            ups: raw.data.ups,
-            downs: raw.data.downs,
-            url: raw.data.url,
-            author: raw.data.author,
-            subreddit: raw.data.subreddit,
-            selftext: raw.data.selftext,
-            id: raw.data.id,
-            kind: raw.kind,
+impl Sorting for SearchSort {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Self::Relevance => "relevance",
+            Self::Hot => "hot",
+            Self::Top => "top",
+            Self::New => "new",
+            Self::Comments => "comments",
         }
     }
 }
 
-// Create a comment from som raw data.
-impl From<RawKind<RawCommentData>> for Comment {
-    fn from(raw: RawKind<RawCommentData>) -> Self {
-        Self {
-            author: raw.data.author,
-            id: raw.data.id,
-            body: raw.data.body,
+/// The order of a user listing, such as [`Redditor::submitted`]. See [`Feed::sort`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UserSort {
+    Hot,
+    New,
+    Top,
+    Controversial,
+}
+
+impl Sorting for UserSort {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Self::Hot => "hot",
+            Self::New => "new",
+            Self::Top => "top",
+            Self::Controversial => "controversial",
         }
     }
 }
 
-// Not used yet
-// pub enum Kind {
-//     Comment,
-//     Account,
-//     Link,
-//     Message,
-//     Subreddit,
-//     Award,
-// }
-
-// impl std::convert::TryFrom<&str> for Kind {
-//     type Error = crate::reddit::Error;
+/// A way of sorting a [`Feed`]. See [`Feed::sort`].
+pub trait Sorting {
+    #[doc(hidden)]
+    fn as_str(&self) -> &'static str;
+}
 
-//     fn try_from(value: &str) -> Result<Self, Self::Error> {
-//         match value {
-//             "t1" => Ok(Self::Comment),
-//             _ => Err(crate::reddit::Error::KindParseError),
-//         }
-//     }
-// }
+/// The syntax of a search query. See [`PostFeed::syntax`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SearchSyntax {
+    Lucene,
+    Cloudsearch,
+    Plain,
+}
 
-// Discard all the JSON data
-#[derive(Deserialize, Debug)]
-struct Empty {}
+impl SearchSyntax {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Self::Lucene => "lucene",
+            Self::Cloudsearch => "cloudsearch",
+            Self::Plain => "plain",
+        }
+    }
+}
 
-// The raw responses from Reddit. The interpreted structs like [`crate::things::Subreddit`] and [`crate::things::Post`] are meant to be used.
-#[doc(hidden)]
-pub mod raw {
-    use serde::Deserialize;
+/// A comment. Its fields are in [`Self::data`].
+/// Serializes as its [`CommentData`].
+#[derive(Debug)]
+pub struct Comment<T: Authenticator> {
+    client: AuthenticatedClient<T>,
+    pub data: CommentData,
+}
 
-    #[derive(Debug, Clone, Deserialize)]
-    pub struct Pagination {
-        pub(crate) after: Option<String>,
-        pub(crate) before: Option<String>,
+impl<T: Authenticator> Clone for Comment<T> {
+    fn clone(&self) -> Self {
+        Self {
+            client: self.client.clone(),
+            data: self.data.clone(),
+        }
     }
+}
 
-    pub mod listing {
-        use super::Pagination;
-        use serde::Deserialize;
+impl<T: Authenticator> Serialize for Comment<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        self.data.serialize(serializer)
+    }
+}
 
-        // Listings from Reddit take this form.
-        #[derive(Debug, Clone, Deserialize)]
-        pub struct RawListing<T> {
-            pub(crate) data: RawListingData<T>,
-        }
+/// The data of a [`Comment`], without the client, so it can be stored or sent to other threads.
+/// Use [`Self::attach`] to interact with the comment again.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CommentData {
+    pub author: String,
+    pub body: String,
+    /// The unique base 36 ID of this comment
+    #[serde(deserialize_with = "raw::base36")]
+    pub id: String,
+    /// The flair of the author in this subreddit, if any.
+    pub author_flair: Option<Flair>,
+    /// The comment this is a reply to, or the post if this is a top level comment.
+    pub parent_id: Fullname,
+    /// The post this comment belongs to.
+    pub link_id: Fullname,
+    /// Upvotes minus downvotes, as shown on Reddit.
+    pub score: i32,
+    #[serde(with = "raw::timestamp")]
+    pub created_utc: SystemTime,
+    /// When this comment was last edited, if ever.
+    #[serde(default, deserialize_with = "raw::edited")]
+    pub edited: Option<Edited>,
+    /// The path of this comment, e.g. `/r/rust/comments/abc123/title/def456/`.
+    pub permalink: String,
+    /// How deeply nested this comment is, 0 for top level comments. Only known in comment sections.
+    pub depth: Option<i32>,
+    /// Whether the author of this comment is the author of the post.
+    pub is_submitter: bool,
+    /// Whether the author has marked this comment as a moderator or admin.
+    pub distinguished: Option<Distinguished>,
+    /// Pinned to the top of the comment section.
+    pub stickied: bool,
+    /// The text of this comment, rendered to HTML.
+    pub body_html: String,
+    /// The subreddit this comment belongs to.
+    pub subreddit: String,
+    /// 1 if this comment has both many upvotes and many downvotes, otherwise 0.
+    pub controversiality: i32,
+    pub total_awards_received: i32,
+    /// The awards given to this comment.
+    pub awards: Vec<Award>,
+}
 
-        #[derive(Debug, Clone, Deserialize)]
-        pub struct RawListingData<T> {
-            #[serde(flatten)]
-            pub(crate) pagination: Pagination,
-            pub(crate) children: Vec<T>,
+impl CommentData {
+    /// Attach the client of `reddit`, to interact with this comment again. See [`PostData::attach`].
+    pub fn attach<T: Authenticator>(self, reddit: &Reddit<T>) -> Comment<T> {
+        Comment {
+            client: reddit.client().clone(),
+            data: self,
         }
     }
+}
 
-    pub mod generic_kind {
-        use serde::Deserialize;
+impl<T: Authenticator> Comment<T> {
+    /// Detach the data of this comment from the client.
+    pub fn into_data(self) -> CommentData {
+        self.data
+    }
 
-        #[derive(Debug, Deserialize)]
-        pub struct RawKind<T> {
-            pub(crate) data: T,
-            pub(crate) kind: String,
-        }
+    /// The fullname of this comment, e.g. `t1_def456`.
+    ///
+    /// # Panics
+    /// If [`CommentData::id`] was changed to something that is not base 36. IDs from Reddit are validated.
+    pub fn fullname(&self) -> Fullname {
+        Fullname::new(Kind::Comment, &self.data.id).expect("Invalid comment ID")
     }
 
-    pub mod post {
-        use serde::Deserialize;
+    /// Whether this is a reply to the post, rather than to another comment.
+    pub fn is_top_level(&self) -> bool {
+        self.data.parent_id.kind() == Kind::Link
+    }
 
-        #[derive(Debug, Clone, Deserialize)]
-        pub struct RawPostData {
-            pub(crate) title: String,
-            pub(crate) ups: i32,
-            pub(crate) downs: i32,
-            pub(crate) url: String,
-            pub(crate) author: String,
-            pub(crate) subreddit: String,
-            pub(crate) selftext: String,
-            pub(crate) id: String,
-        }
+    /// Moderate this comment. Only available if the logged in user moderates the subreddit.
+    pub fn mod_(&self) -> CommentModeration<T> {
+        CommentModeration::create(self.fullname(), &self.client)
     }
+}
 
-    pub mod comment {
-        use serde::Deserialize;
+impl<T: Authenticator> FromListing<T> for Comment<T> {
+    type Raw = RawKind<RawCommentData>;
+
+    fn from_raw(raw: Self::Raw, client: &AuthenticatedClient<T>) -> Result<Self> {
+        Ok(Self::from((raw, client)))
+    }
+}
+
+/// Either a post or a comment, for listings that mix the two, such as [`Redditor::overview`].
+#[derive(Debug)]
+#[allow(clippy::large_enum_variant)]
+pub enum Thing<T: Authenticator> {
+    Post(Post<T>),
+    Comment(Comment<T>),
+}
+
+impl<T: Authenticator> FromListing<T> for Thing<T> {
+    type Raw = RawThing;
+
+    fn from_raw(raw: Self::Raw, client: &AuthenticatedClient<T>) -> Result<Self> {
+        Ok(match raw {
+            RawThing::Post(data) => Self::Post(Post::try_from((
+                RawKind {
+                    data,
+                    kind: String::from("t3"),
+                },
+                client,
+            ))?),
+            RawThing::Comment(data) => Self::Comment(Comment::from((
+                RawKind {
+                    data,
+                    kind: String::from("t1"),
+                },
+                client,
+            ))),
+        })
+    }
+}
+
+/// A set of comments, meant to be iterated over.
+#[derive(Debug)]
+pub struct CommentFeed<T: Authenticator> {
+    url: String,
+    client: AuthenticatedClient<T>,
+    cached_comments: Vec<Comment<T>>,
+}
+impl<T: Authenticator> Iterator for CommentFeed<T> {
+    type Item = Result<Comment<T>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.cached_comments.pop().map(Ok).or_else_transpose(|| {
+            let text = self.client.get(&self.url, None::<&()>)?.text()?;
+
+            // The first listing returned by reddit is the post the comments belong to (smh..), the second listing are the comments.
+            // So we just toss away all the json from the first element of the tuple.
+            let listings: (Empty, RawListing<RawKind<RawCommentData>>) =
+                serde_json::from_str(&text)?;
+
+            let client = &self.client;
+
+            // Add comments to the cached_commments array, converting from RawComment to Comment in the process
+            self.cached_comments.extend(
+                listings
+                    .1
+                    .data
+                    .children
+                    .into_iter()
+                    .rev()
+                    .map(|raw| (raw, client))
+                    .map(From::from),
+            );
+
+            Ok(self.cached_comments.pop())
+        })
+    }
+}
+
+/// A post, comment or subreddit, as returned by [`crate::reddit::Reddit::info`].
+#[derive(Debug)]
+#[allow(clippy::large_enum_variant)]
+pub enum Info<T: Authenticator> {
+    Post(Post<T>),
+    Comment(Comment<T>),
+    Subreddit(SubredditInfo),
+}
+
+impl<T: Authenticator> Info<T> {
+    fn from_raw(raw: RawInfo, client: &AuthenticatedClient<T>) -> Result<Self> {
+        Ok(match raw {
+            RawInfo::Thing(thing) => match Thing::from_raw(thing, client)? {
+                Thing::Post(post) => Self::Post(post),
+                Thing::Comment(comment) => Self::Comment(comment),
+            },
+            RawInfo::Subreddit(subreddit) => Self::Subreddit(subreddit.data),
+        })
+    }
+}
+
+/// Things looked up by their fullnames, meant to be iterated over. See [`crate::reddit::Reddit::info`].
+/// Reddit leaves out things that do not exist, so there may be fewer things than fullnames.
+#[derive(Debug)]
+pub struct InfoFeed<T: Authenticator> {
+    // Not yet requested, in reverse order.
+    fullnames: Vec<Fullname>,
+    cached: Vec<Info<T>>,
+    client: AuthenticatedClient<T>,
+}
+
+impl<T: Authenticator> InfoFeed<T> {
+    pub(crate) fn new(mut fullnames: Vec<Fullname>, client: &AuthenticatedClient<T>) -> Self {
+        fullnames.reverse();
+
+        Self {
+            fullnames,
+            cached: Vec::new(),
+            client: client.clone(),
+        }
+    }
+}
+
+impl<T: Authenticator> Iterator for InfoFeed<T> {
+    type Item = Result<Info<T>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.cached.is_empty() && !self.fullnames.is_empty() {
+            // Reddit allows up to 100 fullnames per request.
+            let batch = self
+                .fullnames
+                .split_off(self.fullnames.len().saturating_sub(100));
+            let ids = batch
+                .iter()
+                .rev()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join(",");
+
+            let text = match self
+                .client
+                .get(
+                    &format!("{}api/info", crate::reddit::URL),
+                    Some(&[("id", ids)]),
+                )
+                .and_then(|response| Ok(response.text()?))
+            {
+                Ok(text) => text,
+                Err(err) => return Some(Err(err)),
+            };
+
+            let listing: RawListing<RawInfo> = match serde_json::from_str(&text) {
+                Ok(listing) => listing,
+                Err(err) => return Some(Err(err.into())),
+            };
+
+            let client = &self.client;
+
+            let things = match listing
+                .data
+                .children
+                .into_iter()
+                .rev()
+                .map(|raw| Info::from_raw(raw, client))
+                .collect::<Result<Vec<_>>>()
+            {
+                Ok(things) => things,
+                Err(err) => return Some(Err(err)),
+            };
+            self.cached.extend(things);
+        }
+
+        self.cached.pop().map(Ok)
+    }
+}
+
+/// Information about the authenticated user
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Me {
+    pub name: String,
+    /// The unique base 36 ID of this user.
+    pub id: String,
+    #[serde(with = "raw::timestamp")]
+    pub created_utc: SystemTime,
+    pub total_karma: i32,
+    pub link_karma: i32,
+    pub comment_karma: i32,
+    pub verified: bool,
+    /// Whether there are unread messages in the inbox.
+    #[serde(default)]
+    pub has_mail: bool,
+    /// The amount of unread messages in the inbox.
+    #[serde(default)]
+    pub inbox_count: i32,
+    /// Whether this user moderates any subreddit.
+    pub is_mod: bool,
+    /// Whether this user has Reddit premium.
+    pub is_gold: bool,
+    #[serde(default, deserialize_with = "raw::non_empty")]
+    pub icon_img: Option<String>,
+}
+
+/// Karma earned in a single subreddit. See [`crate::reddit::Reddit::karma_breakdown`].
+#[derive(Debug, Clone, Deserialize)]
+pub struct SubredditKarma {
+    #[serde(rename = "sr")]
+    pub subreddit: String,
+    pub link_karma: i32,
+    pub comment_karma: i32,
+}
+
+/// A trophy shown on a users profile. See [`crate::reddit::Reddit::trophies`].
+#[derive(Debug, Clone, Deserialize)]
+pub struct Trophy {
+    pub name: String,
+    pub description: Option<String>,
+    /// URL of the 70x70 icon.
+    pub icon_70: String,
+    /// URL of the 40x40 icon.
+    pub icon_40: String,
+    pub award_id: Option<String>,
+    pub url: Option<String>,
+    #[serde(default, with = "raw::timestamp::option")]
+    pub granted_at: Option<SystemTime>,
+}
+
+/// An award given to a post or comment. See [`CommentData::awards`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Award {
+    pub id: String,
+    pub name: String,
+    pub description: Option<String>,
+    /// How many times this award was given.
+    pub count: i32,
+    pub coin_price: i32,
+    /// URL of the icon.
+    pub icon_url: String,
+}
+
+/// The preferences of the logged in user. See [`crate::reddit::Reddit::preferences`].
+/// Fields are None if Reddit did not return them, and are then left unchanged when updating the preferences.
+/// Preferences not known to snew are kept in [`Self::other`].
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Preferences {
+    /// The interface language, e.g. `en`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lang: Option<String>,
+    /// Whether the user is over 18 and wishes to see NSFW content.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub over_18: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub search_include_over_18: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub label_nsfw: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub show_link_flair: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub show_flair: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hide_ups: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hide_downs: Option<bool>,
+    /// Hide the users profile from search engines.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hide_from_robots: Option<bool>,
+    /// Make the votes of the user public.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub public_votes: Option<bool>,
+    /// The sort order of comments when opening a post. Left unchanged when updating if it is [`CommentSort::Other`].
+    #[serde(skip_serializing_if = "is_unknown_sort")]
+    pub default_comment_sort: Option<CommentSort>,
+    /// Hide posts with a lower score than this.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_link_score: Option<i32>,
+    /// Hide comments with a lower score than this.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_comment_score: Option<i32>,
+    /// The amount of comments to show by default.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub num_comments: Option<i32>,
+    /// The amount of posts to show by default.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub numsites: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub show_trending: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub threaded_messages: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub email_messages: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mark_messages_read: Option<bool>,
+    /// Who can send private messages to the user.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub accept_pms: Option<AcceptPms>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub enable_followers: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub show_presence: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nightmode: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub beta: Option<bool>,
+    /// All other preferences, as returned by Reddit.
+    #[serde(flatten)]
+    pub other: serde_json::Map<String, serde_json::Value>,
+}
+
+/// Who can send private messages to a user. See [`Preferences::accept_pms`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AcceptPms {
+    Everyone,
+    /// Only trusted users.
+    Whitelisted,
+}
+
+/// The order of comments on a post.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CommentSort {
+    /// Called 'best' on the website.
+    Confidence,
+    Top,
+    New,
+    Controversial,
+    Old,
+    Random,
+    /// Question and answer.
+    Qa,
+    Live,
+    /// Some sort not known to snew. Can not be sent back to Reddit.
+    #[serde(other)]
+    Other,
+}
+
+impl CommentSort {
+    pub(crate) fn as_str(&self) -> Option<&'static str> {
+        match self {
+            Self::Confidence => Some("confidence"),
+            Self::Top => Some("top"),
+            Self::New => Some("new"),
+            Self::Controversial => Some("controversial"),
+            Self::Old => Some("old"),
+            Self::Random => Some("random"),
+            Self::Qa => Some("qa"),
+            Self::Live => Some("live"),
+            Self::Other => None,
+        }
+    }
+}
+
+// Unknown sorts are not sent back, which leaves the preference unchanged.
+fn is_unknown_sort(sort: &Option<CommentSort>) -> bool {
+    matches!(sort, None | Some(CommentSort::Other))
+}
+
+/// Metadata about a subreddit. See [`Subreddit::about`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SubredditInfo {
+    /// The name used in URLs, e.g. `rust`.
+    pub display_name: String,
+    /// The fullname of this subreddit, e.g. `t5_2s7lj`.
+    pub name: String,
+    pub title: String,
+    /// The short description shown in search results and on the subreddit.
+    #[serde(default)]
+    pub public_description: String,
+    /// The sidebar, in markdown.
+    #[serde(default)]
+    pub description: String,
+    /// The amount of subscribers. Reddit omits this for some subreddits.
+    pub subscribers: Option<i64>,
+    /// The amount of users currently viewing this subreddit.
+    #[serde(alias = "accounts_active")]
+    pub active_user_count: Option<i64>,
+    #[serde(with = "raw::timestamp")]
+    pub created_utc: SystemTime,
+    /// Whether this subreddit is marked NSFW.
+    #[serde(default)]
+    pub over18: bool,
+    /// Who can view and post in this subreddit.
+    pub subreddit_type: SubredditType,
+    /// What kind of posts are allowed.
+    pub submission_type: Option<SubmissionType>,
+    #[serde(default, deserialize_with = "raw::non_empty")]
+    pub icon_img: Option<String>,
+    #[serde(default, deserialize_with = "raw::non_empty")]
+    pub community_icon: Option<String>,
+    #[serde(default, deserialize_with = "raw::non_empty")]
+    pub banner_img: Option<String>,
+    #[serde(default, deserialize_with = "raw::non_empty")]
+    pub banner_background_image: Option<String>,
+    /// The relative URL of this subreddit, e.g. `/r/rust/`.
+    pub url: String,
+    /// Whether the logged in user moderates this subreddit. None if not logged in.
+    pub user_is_moderator: Option<bool>,
+    /// Whether the logged in user is subscribed to this subreddit. None if not logged in.
+    pub user_is_subscriber: Option<bool>,
+    /// Whether the logged in user is banned from this subreddit. None if not logged in.
+    pub user_is_banned: Option<bool>,
+}
+
+/// Who can view and post in a subreddit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SubredditType {
+    Public,
+    /// Only approved users can view the subreddit.
+    Private,
+    /// Anyone can view the subreddit, only approved users can post.
+    Restricted,
+    GoldRestricted,
+    GoldOnly,
+    Archived,
+    EmployeesOnly,
+    /// A user profile.
+    User,
+    /// Some type not known to snew.
+    #[serde(other)]
+    Other,
+}
+
+/// What kind of posts are allowed in a subreddit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SubmissionType {
+    /// Both links and text posts.
+    Any,
+    Link,
+    #[serde(rename = "self")]
+    Text,
+    /// Some type not known to snew.
+    #[serde(other)]
+    Other,
+}
+
+/// A handle to interact with a redditor, i.e. a user.
+/// Use [`crate::reddit::Reddit::redditor`] to create one.
+#[derive(Debug)]
+pub struct Redditor<T: Authenticator> {
+    pub name: String,
+    pub url: String,
+    pub(crate) client: AuthenticatedClient<T>,
+}
+
+impl<T: Authenticator> Redditor<T> {
+    /// Create a instance of a redditor
+    /// Use [`crate::reddit::Reddit::redditor()`] instead.
+    pub fn create(name: &str, client: &AuthenticatedClient<T>) -> Self {
+        Self {
+            name: String::from(name),
+            url: format!("{}user/{}", crate::reddit::URL, name),
+            client: client.clone(),
+        }
+    }
+
+    /// Get information about this redditor, such as karma.
+    pub fn about(&self) -> Result<RedditorInfo> {
+        let text = self
+            .client
+            .get(&format!("{}/about", self.url), None::<&()>)?
+            .text()?;
+
+        let raw: RawKind<RedditorInfo> = serde_json::from_str(&text)?;
+
+        Ok(raw.data)
+    }
+
+    /// The posts and comments of this redditor.
+    pub fn overview(&self) -> Feed<T, Thing<T>> {
+        self.listing("overview")
+    }
+
+    /// The posts of this redditor.
+    pub fn submitted(&self) -> PostFeed<T> {
+        self.listing("submitted")
+    }
+
+    /// The comments of this redditor.
+    pub fn comments(&self) -> Feed<T, Comment<T>> {
+        self.listing("comments")
+    }
+
+    /// The posts and comments of this redditor that have received awards.
+    pub fn gilded(&self) -> Feed<T, Thing<T>> {
+        self.listing("gilded")
+    }
+
+    /// The posts this redditor has upvoted. Only available for the logged in user.
+    pub fn upvoted(&self) -> PostFeed<T> {
+        self.listing("upvoted")
+    }
+
+    /// The posts this redditor has downvoted. Only available for the logged in user.
+    pub fn downvoted(&self) -> PostFeed<T> {
+        self.listing("downvoted")
+    }
+
+    /// The posts and comments this redditor has saved. Only available for the logged in user.
+    pub fn saved(&self) -> Feed<T, Thing<T>> {
+        self.listing("saved")
+    }
+
+    /// The posts this redditor has hidden. Only available for the logged in user.
+    pub fn hidden(&self) -> PostFeed<T> {
+        self.listing("hidden")
+    }
+
+    /// A multireddit owned by this redditor.
+    pub fn multireddit(&self, name: &str) -> Multireddit<T> {
+        Multireddit::create(&self.name, name, &self.client)
+    }
+
+    /// The public multireddits of this redditor.
+    pub fn multireddits(&self) -> Result<Vec<MultiredditInfo>> {
+        multireddit::list(
+            &self.client,
+            &format!("{}api/multi/user/{}", crate::reddit::URL, self.name),
+        )
+    }
+
+    fn listing<I>(&self, path: &str) -> Feed<T, I> {
+        Feed::new(format!("{}/{}", self.url, path), &self.client)
+    }
+}
+
+/// Information about a redditor. See [`Redditor::about`].
+/// Suspended accounts only have a name, in which case all other fields have their default values.
+#[derive(Debug, Clone, Deserialize)]
+pub struct RedditorInfo {
+    pub name: String,
+    /// The unique base 36 ID of this redditor.
+    #[serde(default)]
+    pub id: String,
+    #[serde(default)]
+    pub total_karma: i32,
+    #[serde(default)]
+    pub link_karma: i32,
+    #[serde(default)]
+    pub comment_karma: i32,
+    #[serde(default)]
+    pub awardee_karma: i32,
+    #[serde(default)]
+    pub awarder_karma: i32,
+    #[serde(default, with = "raw::timestamp::option")]
+    pub created_utc: Option<SystemTime>,
+    /// Whether this redditor moderates any subreddit.
+    #[serde(default)]
+    pub is_mod: bool,
+    /// Whether this redditor has Reddit premium.
+    #[serde(default)]
+    pub is_gold: bool,
+    #[serde(default)]
+    pub is_employee: bool,
+    #[serde(default)]
+    pub verified: bool,
+    #[serde(default)]
+    pub is_suspended: bool,
+    #[serde(default, deserialize_with = "raw::non_empty")]
+    pub icon_img: Option<String>,
+}
+
+impl<T: Authenticator> FromListing<T> for SubredditInfo {
+    type Raw = RawKind<SubredditInfo>;
+
+    fn from_raw(raw: Self::Raw, _: &AuthenticatedClient<T>) -> Result<Self> {
+        Ok(raw.data)
+    }
+}
+
+pub trait Transpose<T> {
+    fn or_else_transpose<F: FnOnce() -> Result<Option<T>>>(self, f: F) -> Option<Result<T>>;
+}
+
+impl<T> Transpose<T> for Option<Result<T>> {
+    fn or_else_transpose<F: FnOnce() -> Result<Option<T>>>(self, f: F) -> Option<Result<T>> {
+        if self.is_none() {
+            f().transpose()
+        } else {
+            self
+        }
+    }
+}
+
+// fn or_else_transpose<T, F>(opt: Option<Result<T>>, f: F) -> Option<Result<T>>
+// where
+//     F: FnOnce() -> Result<Option<T>>,
+// {
+//     if let None = opt {
+//         f().transpose()
+//     } else {
+//         opt
+//     }
+// }
+
+// Create a post from som raw data. Errors if the kind is not t3.
+impl<T: Authenticator> TryFrom<(RawKind<RawPostData>, &AuthenticatedClient<T>)> for Post<T> {
+    type Error = Error;
+
+    fn try_from(raw: (RawKind<RawPostData>, &AuthenticatedClient<T>)) -> Result<Self> {
+        let (raw, client) = raw;
+
+        Ok(Self {
+            client: client.clone(),
+            data: PostData::try_from(raw)?,
+        })
+    }
+}
+
+impl TryFrom<RawKind<RawPostData>> for PostData {
+    type Error = Error;
+
+    fn try_from(raw: RawKind<RawPostData>) -> Result<Self> {
+        let kind = raw.kind.parse()?;
+        if kind != Kind::Link {
+            return Err(Error::FullnameParseError(format!(
+                "Expected a post, got kind {}",
+                kind
+            )));
+        }
+
+        Ok(Self {
+            title: raw.data.title,
+            ups: raw.data.ups,
+            downs: raw.data.downs,
+            url: raw.data.url,
+            author: raw.data.author,
+            subreddit: raw.data.subreddit,
+            selftext: raw.data.selftext,
+            id: raw.data.id,
+            kind,
+            link_flair: raw.data.link_flair.into(),
+            author_flair: raw.data.author_flair.into(),
+            score: raw.data.score,
+            upvote_ratio: raw.data.upvote_ratio,
+            num_comments: raw.data.num_comments,
+            created_utc: raw.data.created_utc,
+            edited: raw.data.edited,
+            permalink: raw.data.permalink,
+            domain: raw.data.domain,
+            over_18: raw.data.over_18,
+            spoiler: raw.data.spoiler,
+            stickied: raw.data.stickied,
+            locked: raw.data.locked,
+            archived: raw.data.archived,
+            is_self: raw.data.is_self,
+            is_video: raw.data.is_video,
+            distinguished: raw.data.distinguished,
+            author_fullname: raw.data.author_fullname,
+            subreddit_id: raw.data.subreddit_id,
+            thumbnail: raw.data.thumbnail,
+            selftext_html: raw.data.selftext_html,
+            preview: raw.data.preview,
+            media: raw.data.media,
+            secure_media: raw.data.secure_media,
+            gallery: media::gallery(raw.data.gallery_data, raw.data.media_metadata),
+        })
+    }
+}
+
+// Create a comment from som raw data.
+impl<T: Authenticator> From<(RawKind<RawCommentData>, &AuthenticatedClient<T>)> for Comment<T> {
+    fn from(raw: (RawKind<RawCommentData>, &AuthenticatedClient<T>)) -> Self {
+        let (raw, client) = raw;
+        Self {
+            client: client.clone(),
+            data: CommentData {
+                author: raw.data.author,
+                id: raw.data.id,
+                body: raw.data.body,
+                author_flair: raw.data.author_flair.into(),
+                parent_id: raw.data.parent_id,
+                link_id: raw.data.link_id,
+                score: raw.data.score,
+                created_utc: raw.data.created_utc,
+                edited: raw.data.edited,
+                permalink: raw.data.permalink,
+                depth: raw.data.depth,
+                is_submitter: raw.data.is_submitter,
+                distinguished: raw.data.distinguished,
+                stickied: raw.data.stickied,
+                body_html: raw.data.body_html,
+                subreddit: raw.data.subreddit,
+                controversiality: raw.data.controversiality,
+                total_awards_received: raw.data.total_awards_received,
+                awards: raw.data.all_awardings,
+            },
+        }
+    }
+}
+
+/// The type of a thing, the part before the underscore in a fullname such as `t3_abc123`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum Kind {
+    /// t1
+    #[serde(rename = "t1")]
+    Comment,
+    /// t2, a redditor.
+    #[serde(rename = "t2")]
+    Account,
+    /// t3, a post.
+    #[serde(rename = "t3")]
+    Link,
+    /// t4, a private message.
+    #[serde(rename = "t4")]
+    Message,
+    /// t5
+    #[serde(rename = "t5")]
+    Subreddit,
+    /// t6
+    #[serde(rename = "t6")]
+    Award,
+}
+
+impl Kind {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Self::Comment => "t1",
+            Self::Account => "t2",
+            Self::Link => "t3",
+            Self::Message => "t4",
+            Self::Subreddit => "t5",
+            Self::Award => "t6",
+        }
+    }
+}
+
+impl FromStr for Kind {
+    type Err = Error;
+
+    fn from_str(value: &str) -> Result<Self> {
+        match value {
+            "t1" => Ok(Self::Comment),
+            "t2" => Ok(Self::Account),
+            "t3" => Ok(Self::Link),
+            "t4" => Ok(Self::Message),
+            "t5" => Ok(Self::Subreddit),
+            "t6" => Ok(Self::Award),
+            _ => Err(Error::FullnameParseError(format!("Unknown kind {}", value))),
+        }
+    }
+}
+
+impl fmt::Display for Kind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// The fullname of a thing, e.g. `t3_abc123`, which is its [`Kind`] and base 36 ID combined. Fullnames are unique across Reddit.
+/// Fullnames order by kind, then by ID, which for posts and comments is the order they were created in.
+/// ```
+/// # fn main() -> snew::reddit::Result<()> {
+/// use snew::things::{Fullname, Kind};
+///
+/// let post: Fullname = "t3_abc123".parse()?;
+/// assert_eq!(post.kind(), Kind::Link);
+/// assert_eq!(post.id(), "abc123");
+/// assert_eq!(post.to_string(), "t3_abc123");
+///
+/// let comment = Fullname::from_url("https://old.reddit.com/r/rust/comments/abc123/title/def456/?context=3")?;
+/// assert_eq!(comment, Fullname::new(Kind::Comment, "def456")?);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Deserialize)]
+#[serde(try_from = "String")]
+pub struct Fullname {
+    kind: Kind,
+    id: String,
+    // The ID as a number, validated when created.
+    number: u64,
+}
+
+impl Fullname {
+    /// Combine a kind and a base 36 ID. Errors if the ID is not base 36.
+    pub fn new(kind: Kind, id: &str) -> Result<Self> {
+        Ok(Self {
+            kind,
+            id: String::from(id),
+            number: id_to_number(id)?,
+        })
+    }
+
+    /// Find the post or comment a Reddit URL links to. Supports permalinks from www, old and new Reddit,
+    /// links without a subreddit such as `reddit.com/comments/abc123`, and short links such as `https://redd.it/abc123`.
+    pub fn from_url(url: &str) -> Result<Self> {
+        let error =
+            || Error::FullnameParseError(format!("Not a link to a post or comment: {}", url));
+
+        // Ignore the scheme, query and fragment.
+        let url = url.split(['?', '#']).next().unwrap_or(url);
+        let url = url.split("://").last().unwrap_or(url);
+        let mut segments = url.split('/').filter(|segment| !segment.is_empty());
+
+        let host = segments.next().ok_or_else(error)?;
+        if host == "redd.it" {
+            return Self::new(Kind::Link, segments.next().ok_or_else(error)?);
+        }
+        if host != "reddit.com" && !host.ends_with(".reddit.com") {
+            return Err(error());
+        }
+
+        // /r/<subreddit>/comments/<post>/<title>/<comment>
+        let mut segments = segments
+            .skip_while(|segment| *segment != "comments")
+            .skip(1);
+        let post = segments.next().ok_or_else(error)?;
+
+        match segments.nth(1) {
+            Some(comment) => Self::new(Kind::Comment, comment),
+            None => Self::new(Kind::Link, post),
+        }
+    }
+
+    pub fn kind(&self) -> Kind {
+        self.kind
+    }
+
+    /// The base 36 ID.
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// The ID as a number. See [`id_to_number`].
+    pub fn number(&self) -> u64 {
+        self.number
+    }
+}
+
+impl FromStr for Fullname {
+    type Err = Error;
+
+    fn from_str(value: &str) -> Result<Self> {
+        let (kind, id) = value
+            .split_once('_')
+            .ok_or_else(|| Error::FullnameParseError(format!("Missing underscore in {}", value)))?;
+
+        Self::new(kind.parse()?, id)
+    }
+}
+
+impl TryFrom<String> for Fullname {
+    type Error = Error;
+
+    fn try_from(value: String) -> Result<Self> {
+        value.parse()
+    }
+}
+
+impl Serialize for Fullname {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl fmt::Display for Fullname {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}_{}", self.kind, self.id)
+    }
+}
+
+// IDs with leading zeros are the same thing, so compare by number rather than ID, like Ord does.
+impl PartialEq for Fullname {
+    fn eq(&self, other: &Self) -> bool {
+        (self.kind, self.number) == (other.kind, other.number)
+    }
+}
+
+impl Eq for Fullname {}
+
+impl Hash for Fullname {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        (self.kind, self.number).hash(state);
+    }
+}
+
+impl PartialOrd for Fullname {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Fullname {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.kind, self.number).cmp(&(other.kind, other.number))
+    }
+}
+
+/// When a post or comment was last edited. See [`PostData::edited`].
+/// Serializes the way Reddit sends it, as a timestamp or `true`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Edited {
+    At(SystemTime),
+    /// Edited, but Reddit does not know when. Only some old posts and comments.
+    Unknown,
+}
+
+impl Serialize for Edited {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        match self {
+            Self::At(time) => raw::timestamp::serialize(time, serializer),
+            Self::Unknown => serializer.serialize_bool(true),
+        }
+    }
+}
+
+/// Marks a post or comment as made by a moderator or admin, acting as such.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Distinguished {
+    Moderator,
+    Admin,
+    /// Special distinctions given by admins.
+    Special,
+    #[serde(other)]
+    Other,
+}
+
+/// Convert a base 36 ID, e.g. `abc123`, to a number. IDs are handed out in increasing order, so this can be used to sort things by age.
+pub fn id_to_number(id: &str) -> Result<u64> {
+    // from_str_radix accepts a leading +, and upper case letters, which are not valid in IDs.
+    if id.is_empty()
+        || !id
+            .bytes()
+            .all(|byte| byte.is_ascii_digit() || byte.is_ascii_lowercase())
+    {
+        return Err(Error::FullnameParseError(format!(
+            "Not a base 36 ID: {}",
+            id
+        )));
+    }
+
+    u64::from_str_radix(id, 36).map_err(|err| Error::FullnameParseError(format!("{}: {}", err, id)))
+}
+
+// Discard all the JSON data
+#[derive(Deserialize, Debug)]
+struct Empty {}
+
+// The raw responses from Reddit. The interpreted structs like [`crate::things::Subreddit`] and [`crate::things::Post`] are meant to be used.
+#[doc(hidden)]
+pub mod raw {
+    use std::time::SystemTime;
+
+    use serde::{Deserialize, Deserializer};
+
+    use super::{Edited, SubredditInfo};
+
+    // Reddit uses an empty string for some missing values, such as images.
+    pub(crate) fn non_empty<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<String>, D::Error> {
+        let value: Option<String> = Deserialize::deserialize(deserializer)?;
+
+        Ok(value.filter(|value| !value.is_empty()))
+    }
+
+    // Reddit uses placeholders such as "self" or "default" for some missing images.
+    pub(crate) fn image_url<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<String>, D::Error> {
+        let value: Option<String> = Deserialize::deserialize(deserializer)?;
+
+        Ok(value.filter(|value| value.starts_with("http")))
+    }
+
+    // Validates an ID, so a fullname can always be made from it.
+    pub(crate) fn base36<'de, D: Deserializer<'de>>(deserializer: D) -> Result<String, D::Error> {
+        let id = String::deserialize(deserializer)?;
+        super::id_to_number(&id).map_err(serde::de::Error::custom)?;
+
+        Ok(id)
+    }
+
+    // Edited is false (or null in serialized data) for things that were never edited, otherwise the timestamp of the last edit.
+    // Some old things are only marked as edited with true, without a timestamp.
+    pub(crate) fn edited<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<Edited>, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum RawEdited {
+            Flag(bool),
+            At(#[serde(with = "timestamp")] SystemTime),
+        }
+
+        Ok(match Option::<RawEdited>::deserialize(deserializer)? {
+            None | Some(RawEdited::Flag(false)) => None,
+            Some(RawEdited::Flag(true)) => Some(Edited::Unknown),
+            Some(RawEdited::At(time)) => Some(Edited::At(time)),
+        })
+    }
+
+    // Modmail has ISO 8601 timestamps, e.g. 2021-06-01T12:34:56.789000+00:00, rather than seconds since the unix epoch.
+    pub(crate) mod iso8601 {
+        use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+        use serde::{de, Deserialize, Deserializer};
+
+        pub(crate) fn deserialize<'de, D: Deserializer<'de>>(
+            deserializer: D,
+        ) -> Result<SystemTime, D::Error> {
+            let value = String::deserialize(deserializer)?;
+
+            parse(&value).ok_or_else(|| de::Error::custom(format!("invalid timestamp {}", value)))
+        }
+
+        // For timestamps that can be null or missing, use together with #[serde(default)].
+        pub(crate) mod option {
+            use std::time::SystemTime;
+
+            use serde::{Deserialize, Deserializer};
+
+            pub(crate) fn deserialize<'de, D: Deserializer<'de>>(
+                deserializer: D,
+            ) -> Result<Option<SystemTime>, D::Error> {
+                #[derive(Deserialize)]
+                struct Timestamp(#[serde(deserialize_with = "super::deserialize")] SystemTime);
+
+                let timestamp: Option<Timestamp> = Deserialize::deserialize(deserializer)?;
+
+                Ok(timestamp.map(|Timestamp(time)| time))
+            }
+        }
+
+        // Parses the RFC 3339 profile of ISO 8601: a date, a time with optional fractional seconds, and Z or an offset.
+        fn parse(value: &str) -> Option<SystemTime> {
+            let (date, time) = value.split_once(['T', 't'])?;
+
+            let mut date = date.split('-');
+            let year = number(date.next()?, 4)?;
+            let month = number(date.next()?, 2)?;
+            let day = number(date.next()?, 2)?;
+            if date.next().is_some() || !(1..=12).contains(&month) || !(1..=31).contains(&day) {
+                return None;
+            }
+
+            // The offset starts at the first Z, + or -, as the clock time has none of them.
+            let (clock, offset) = time.split_at(time.find(['Z', 'z', '+', '-'])?);
+            let (clock, fraction) = match clock.split_once('.') {
+                Some((clock, fraction)) => (clock, Some(fraction)),
+                None => (clock, None),
+            };
+
+            let mut clock = clock.split(':');
+            let hour = number(clock.next()?, 2)?;
+            let minute = number(clock.next()?, 2)?;
+            // 60 for leap seconds.
+            let second = number(clock.next()?, 2)?;
+            if clock.next().is_some() || hour > 23 || minute > 59 || second > 60 {
+                return None;
+            }
+
+            // Nanosecond precision, any further digits are ignored.
+            let nanos = match fraction {
+                Some(fraction) => {
+                    if !fraction.bytes().all(|b| b.is_ascii_digit()) {
+                        return None;
+                    }
+                    let digits = &fraction[..fraction.len().min(9)];
+                    number(digits, digits.len())? * 10_u64.pow(9 - digits.len() as u32)
+                }
+                None => 0,
+            };
+
+            let offset = match offset {
+                "Z" | "z" => 0,
+                _ => {
+                    let sign = if offset.starts_with('-') { -1 } else { 1 };
+                    let (hours, minutes) = offset[1..].split_once(':')?;
+                    let (hours, minutes) = (number(hours, 2)?, number(minutes, 2)?);
+                    if hours > 23 || minutes > 59 {
+                        return None;
+                    }
+                    sign * (hours * 3600 + minutes * 60) as i64
+                }
+            };
+
+            let seconds = days_from_civil(year as i64, month as i64, day as i64) * 86400
+                + (hour * 3600 + minute * 60 + second) as i64
+                - offset;
+
+            let time = if seconds >= 0 {
+                UNIX_EPOCH.checked_add(Duration::from_secs(seconds as u64))?
+            } else {
+                UNIX_EPOCH.checked_sub(Duration::from_secs(seconds.unsigned_abs()))?
+            };
+
+            time.checked_add(Duration::from_nanos(nanos))
+        }
+
+        // A number of exactly len ASCII digits.
+        fn number(value: &str, len: usize) -> Option<u64> {
+            if value.len() != len || len == 0 || !value.bytes().all(|b| b.is_ascii_digit()) {
+                return None;
+            }
+
+            value.parse().ok()
+        }
+
+        // Days since 1970-01-01 of a date in the proleptic Gregorian calendar.
+        // See http://howardhinnant.github.io/date_algorithms.html#days_from_civil
+        fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+            let year = if month <= 2 { year - 1 } else { year };
+            let era = if year >= 0 { year } else { year - 399 } / 400;
+            let year_of_era = year - era * 400;
+            let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+            let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+
+            era * 146097 + day_of_era - 719468
+        }
+    }
+
+    // Timestamps are seconds since the unix epoch, as floats.
+    pub(crate) mod timestamp {
+        use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+        use serde::{de, ser, Deserialize, Deserializer, Serializer};
+
+        pub(crate) fn deserialize<'de, D: Deserializer<'de>>(
+            deserializer: D,
+        ) -> Result<SystemTime, D::Error> {
+            let seconds = f64::deserialize(deserializer)?;
+
+            // Duration::from_secs_f64 panics on negative, infinite or too large values.
+            if !seconds.is_finite() || seconds < 0.0 || seconds >= u64::MAX as f64 {
+                return Err(de::Error::custom(format!("invalid timestamp {}", seconds)));
+            }
+
+            Ok(UNIX_EPOCH + Duration::from_secs_f64(seconds))
+        }
+
+        pub(crate) fn serialize<S: Serializer>(
+            time: &SystemTime,
+            serializer: S,
+        ) -> Result<S::Ok, S::Error> {
+            let duration = time
+                .duration_since(UNIX_EPOCH)
+                .map_err(ser::Error::custom)?;
+
+            serializer.serialize_f64(duration.as_secs_f64())
+        }
+
+        // For timestamps that can be null or missing, use together with #[serde(default)].
+        pub(crate) mod option {
+            use std::time::SystemTime;
+
+            use serde::{Deserialize, Deserializer};
+
+            pub(crate) fn deserialize<'de, D: Deserializer<'de>>(
+                deserializer: D,
+            ) -> Result<Option<SystemTime>, D::Error> {
+                #[derive(Deserialize)]
+                struct Timestamp(#[serde(with = "super")] SystemTime);
+
+                let timestamp: Option<Timestamp> = Deserialize::deserialize(deserializer)?;
+
+                Ok(timestamp.map(|Timestamp(time)| time))
+            }
+        }
+    }
+
+    #[derive(Debug, Deserialize)]
+    pub struct RawTrophyList {
+        pub(crate) trophies: Vec<generic_kind::RawKind<super::Trophy>>,
+    }
+
+    // A child of a listing that can be either a post or a comment.
+    #[derive(Debug, Deserialize)]
+    #[serde(tag = "kind", content = "data")]
+    #[allow(clippy::large_enum_variant)]
+    pub enum RawThing {
+        #[serde(rename = "t1")]
+        Comment(comment::RawCommentData),
+        #[serde(rename = "t3")]
+        Post(post::RawPostData),
+    }
+
+    // A child of the listing returned by /api/info, a post, comment or subreddit.
+    #[derive(Debug, Deserialize)]
+    #[serde(untagged)]
+    #[allow(clippy::large_enum_variant)]
+    pub enum RawInfo {
+        Thing(RawThing),
+        Subreddit(generic_kind::RawKind<SubredditInfo>),
+    }
+
+    #[derive(Debug, Clone, Deserialize)]
+    #[allow(dead_code)]
+    pub struct Pagination {
+        pub(crate) after: Option<String>,
+        pub(crate) before: Option<String>,
+    }
+
+    pub mod listing {
+        use super::Pagination;
+        use serde::Deserialize;
+
+        // Listings from Reddit take this form.
+        #[derive(Debug, Clone, Deserialize)]
+        pub struct RawListing<T> {
+            pub(crate) data: RawListingData<T>,
+        }
+
+        #[derive(Debug, Clone, Deserialize)]
+        pub struct RawListingData<T> {
+            #[serde(flatten)]
+            pub(crate) pagination: Pagination,
+            pub(crate) children: Vec<T>,
+        }
+    }
+
+    pub mod generic_kind {
+        use serde::Deserialize;
+
+        #[derive(Debug, Deserialize)]
+        pub struct RawKind<T> {
+            pub(crate) data: T,
+            pub(crate) kind: String,
+        }
+    }
+
+    pub mod flair {
+        use serde::Deserialize;
+
+        use crate::flair::{Flair, FlairRichtext};
+
+        // Flair is spread over several fields of a post or comment, with a prefix.
+        #[derive(Debug, Clone, Deserialize)]
+        pub struct RawLinkFlair {
+            #[serde(default, deserialize_with = "super::non_empty")]
+            pub(crate) link_flair_text: Option<String>,
+            #[serde(default, deserialize_with = "super::non_empty")]
+            pub(crate) link_flair_css_class: Option<String>,
+            pub(crate) link_flair_template_id: Option<String>,
+            #[serde(default)]
+            pub(crate) link_flair_richtext: Vec<FlairRichtext>,
+            #[serde(default, deserialize_with = "super::non_empty")]
+            pub(crate) link_flair_background_color: Option<String>,
+            pub(crate) link_flair_text_color: Option<String>,
+        }
+
+        #[derive(Debug, Clone, Deserialize)]
+        pub struct RawAuthorFlair {
+            #[serde(default, deserialize_with = "super::non_empty")]
+            pub(crate) author_flair_text: Option<String>,
+            #[serde(default, deserialize_with = "super::non_empty")]
+            pub(crate) author_flair_css_class: Option<String>,
+            pub(crate) author_flair_template_id: Option<String>,
+            #[serde(default)]
+            pub(crate) author_flair_richtext: Vec<FlairRichtext>,
+            #[serde(default, deserialize_with = "super::non_empty")]
+            pub(crate) author_flair_background_color: Option<String>,
+            pub(crate) author_flair_text_color: Option<String>,
+        }
+
+        // None if the thing has no flair at all.
+        fn flair(
+            text: Option<String>,
+            css_class: Option<String>,
+            template_id: Option<String>,
+            richtext: Vec<FlairRichtext>,
+            background_color: Option<String>,
+            text_color: Option<String>,
+        ) -> Option<Flair> {
+            if text.is_none() && css_class.is_none() && template_id.is_none() && richtext.is_empty()
+            {
+                None
+            } else {
+                Some(Flair {
+                    text,
+                    css_class,
+                    template_id,
+                    richtext,
+                    background_color,
+                    text_color,
+                })
+            }
+        }
+
+        impl From<RawLinkFlair> for Option<Flair> {
+            fn from(raw: RawLinkFlair) -> Self {
+                flair(
+                    raw.link_flair_text,
+                    raw.link_flair_css_class,
+                    raw.link_flair_template_id,
+                    raw.link_flair_richtext,
+                    raw.link_flair_background_color,
+                    raw.link_flair_text_color,
+                )
+            }
+        }
+
+        impl From<RawAuthorFlair> for Option<Flair> {
+            fn from(raw: RawAuthorFlair) -> Self {
+                flair(
+                    raw.author_flair_text,
+                    raw.author_flair_css_class,
+                    raw.author_flair_template_id,
+                    raw.author_flair_richtext,
+                    raw.author_flair_background_color,
+                    raw.author_flair_text_color,
+                )
+            }
+        }
+    }
+
+    pub mod post {
+        use std::{collections::HashMap, time::SystemTime};
+
+        use serde::Deserialize;
+
+        use super::{
+            flair::{RawAuthorFlair, RawLinkFlair},
+            media::{RawGalleryData, RawMediaMetadata},
+        };
+        use crate::{
+            media::{Media, Preview},
+            things::{Distinguished, Edited, Fullname},
+        };
+
+        #[derive(Debug, Clone, Deserialize)]
+        pub struct RawPostData {
+            #[serde(flatten)]
+            pub(crate) link_flair: RawLinkFlair,
+            #[serde(flatten)]
+            pub(crate) author_flair: RawAuthorFlair,
+            pub(crate) title: String,
+            pub(crate) ups: i32,
+            pub(crate) downs: i32,
+            pub(crate) url: String,
+            pub(crate) author: String,
+            pub(crate) subreddit: String,
+            pub(crate) selftext: String,
+            #[serde(deserialize_with = "super::base36")]
+            pub(crate) id: String,
+            pub(crate) score: i32,
+            pub(crate) upvote_ratio: f64,
+            pub(crate) num_comments: i32,
+            #[serde(with = "super::timestamp")]
+            pub(crate) created_utc: SystemTime,
+            #[serde(default, deserialize_with = "super::edited")]
+            pub(crate) edited: Option<Edited>,
+            pub(crate) permalink: String,
+            pub(crate) domain: String,
+            pub(crate) over_18: bool,
+            pub(crate) spoiler: bool,
+            pub(crate) stickied: bool,
+            pub(crate) locked: bool,
+            pub(crate) archived: bool,
+            pub(crate) is_self: bool,
+            pub(crate) is_video: bool,
+            pub(crate) distinguished: Option<Distinguished>,
+            pub(crate) author_fullname: Option<Fullname>,
+            pub(crate) subreddit_id: Fullname,
+            #[serde(default, deserialize_with = "super::image_url")]
+            pub(crate) thumbnail: Option<String>,
+            pub(crate) selftext_html: Option<String>,
+            pub(crate) preview: Option<Preview>,
+            pub(crate) media: Option<Media>,
+            pub(crate) secure_media: Option<Media>,
+            pub(crate) gallery_data: Option<RawGalleryData>,
+            pub(crate) media_metadata: Option<HashMap<String, RawMediaMetadata>>,
+        }
+    }
+
+    pub mod media {
+        use serde::Deserialize;
+
+        #[derive(Debug, Clone, Deserialize)]
+        pub struct RawGalleryData {
+            pub(crate) items: Vec<RawGalleryItem>,
+        }
+
+        #[derive(Debug, Clone, Deserialize)]
+        pub struct RawGalleryItem {
+            pub(crate) media_id: String,
+            pub(crate) caption: Option<String>,
+            pub(crate) outbound_url: Option<String>,
+        }
+
+        // Reddit abbreviates the fields: m is the mime type, s the source and p the previews.
+        #[derive(Debug, Clone, Deserialize)]
+        pub struct RawMediaMetadata {
+            pub(crate) m: Option<String>,
+            pub(crate) s: Option<RawMediaSource>,
+            #[serde(default)]
+            pub(crate) p: Vec<RawMediaSource>,
+        }
+
+        // x and y are the width and height.
+        #[derive(Debug, Clone, Deserialize)]
+        pub struct RawMediaSource {
+            pub(crate) u: Option<String>,
+            pub(crate) gif: Option<String>,
+            pub(crate) mp4: Option<String>,
+            pub(crate) x: u32,
+            pub(crate) y: u32,
+        }
+    }
+
+    pub mod comment {
+        use std::time::SystemTime;
+
+        use serde::Deserialize;
+
+        use super::flair::RawAuthorFlair;
+        use crate::things::{Award, Distinguished, Edited, Fullname};
 
         #[derive(Debug, Clone, Deserialize)]
         pub struct RawCommentData {
+            #[serde(flatten)]
+            pub(crate) author_flair: RawAuthorFlair,
             pub(crate) author: String,
             pub(crate) body: String,
+            #[serde(deserialize_with = "super::base36")]
+            pub(crate) id: String,
+            pub(crate) parent_id: Fullname,
+            pub(crate) link_id: Fullname,
+            pub(crate) score: i32,
+            #[serde(with = "super::timestamp")]
+            pub(crate) created_utc: SystemTime,
+            #[serde(default, deserialize_with = "super::edited")]
+            pub(crate) edited: Option<Edited>,
+            pub(crate) permalink: String,
+            pub(crate) depth: Option<i32>,
+            #[serde(default)]
+            pub(crate) is_submitter: bool,
+            pub(crate) distinguished: Option<Distinguished>,
+            #[serde(default)]
+            pub(crate) stickied: bool,
+            pub(crate) body_html: String,
+            pub(crate) subreddit: String,
+            #[serde(default)]
+            pub(crate) controversiality: i32,
+            #[serde(default)]
+            pub(crate) total_awards_received: i32,
+            #[serde(default)]
+            pub(crate) all_awardings: Vec<Award>,
+        }
+    }
+
+    pub mod modmail {
+        use std::{collections::HashMap, time::SystemTime};
+
+        use serde::Deserialize;
+
+        use crate::modmail::{ConversationStatus, ModmailAction, ModmailAuthor, ModmailMessage};
+
+        // A page of conversations. The conversations, and their messages, are keyed by id.
+        #[derive(Debug, Deserialize)]
+        #[serde(rename_all = "camelCase")]
+        pub struct RawConversations {
+            pub(crate) conversations: HashMap<String, RawConversation>,
+            pub(crate) conversation_ids: Vec<String>,
+            #[serde(default)]
+            pub(crate) messages: HashMap<String, ModmailMessage>,
+        }
+
+        // A single conversation, with all its messages and mod actions.
+        #[derive(Debug, Deserialize)]
+        #[serde(rename_all = "camelCase")]
+        pub struct RawConversationDetails {
+            pub(crate) conversation: RawConversation,
+            #[serde(default)]
+            pub(crate) messages: HashMap<String, ModmailMessage>,
+            #[serde(default)]
+            pub(crate) mod_actions: HashMap<String, ModmailAction>,
+        }
+
+        #[derive(Debug, Deserialize)]
+        #[serde(rename_all = "camelCase")]
+        pub struct RawConversation {
+            pub(crate) id: String,
+            pub(crate) subject: String,
+            pub(crate) state: ConversationStatus,
+            #[serde(default, deserialize_with = "super::iso8601::option::deserialize")]
+            pub(crate) last_updated: Option<SystemTime>,
+            pub(crate) is_highlighted: bool,
+            pub(crate) is_internal: bool,
+            pub(crate) is_auto: bool,
+            pub(crate) num_messages: i32,
+            pub(crate) owner: RawOwner,
+            // An empty object if there is no participant.
+            pub(crate) participant: Option<ModmailAuthor>,
+            #[serde(default)]
+            pub(crate) authors: Vec<ModmailAuthor>,
+            // The messages and mod actions of the conversation, in order.
+            pub(crate) obj_ids: Vec<RawObjId>,
+        }
+
+        #[derive(Debug, Deserialize)]
+        #[serde(rename_all = "camelCase")]
+        pub struct RawOwner {
+            pub(crate) display_name: String,
+        }
+
+        #[derive(Debug, Deserialize)]
+        pub struct RawObjId {
+            pub(crate) id: String,
+            pub(crate) key: String,
+        }
+    }
+
+    pub mod wiki {
+        use std::time::SystemTime;
+
+        use serde::Deserialize;
+
+        use super::generic_kind::RawKind;
+        use crate::wiki::WikiPermLevel;
+
+        // Wiki responses include the whole user, but only the name is interesting.
+        #[derive(Debug, Deserialize)]
+        pub struct RawWikiUser {
+            pub(crate) name: String,
+        }
+
+        #[derive(Debug, Deserialize)]
+        pub struct RawWikiPage {
+            pub(crate) content_md: String,
+            pub(crate) content_html: String,
+            pub(crate) revision_id: Option<String>,
+            #[serde(default, with = "super::timestamp::option")]
+            pub(crate) revision_date: Option<SystemTime>,
+            pub(crate) revision_by: Option<RawKind<RawWikiUser>>,
+            #[serde(default)]
+            pub(crate) may_revise: bool,
+            pub(crate) reason: Option<String>,
+        }
+
+        #[derive(Debug, Deserialize)]
+        pub struct RawWikiRevision {
+            pub(crate) id: String,
+            pub(crate) page: String,
+            #[serde(with = "super::timestamp")]
+            pub(crate) timestamp: SystemTime,
+            pub(crate) reason: Option<String>,
+            pub(crate) author: Option<RawKind<RawWikiUser>>,
+            #[serde(default)]
+            pub(crate) revision_hidden: bool,
+        }
+
+        #[derive(Debug, Deserialize)]
+        pub struct RawWikiPageSettings {
+            pub(crate) permlevel: WikiPermLevel,
+            pub(crate) listed: bool,
+            #[serde(default)]
+            pub(crate) editors: Vec<RawKind<RawWikiUser>>,
+        }
+    }
+
+    pub mod multireddit {
+        use std::time::SystemTime;
+
+        use serde::Deserialize;
+
+        use crate::multireddit::Visibility;
+
+        #[derive(Debug, Deserialize)]
+        pub struct RawMultireddit {
+            pub(crate) name: String,
+            pub(crate) display_name: String,
+            pub(crate) owner: String,
+            pub(crate) path: String,
+            #[serde(default)]
+            pub(crate) description_md: String,
+            pub(crate) subreddits: Vec<RawMultiredditSubreddit>,
+            pub(crate) visibility: Visibility,
+            #[serde(default)]
+            pub(crate) over_18: bool,
+            #[serde(default)]
+            pub(crate) num_subscribers: i32,
+            #[serde(default, deserialize_with = "super::non_empty")]
+            pub(crate) icon_url: Option<String>,
+            #[serde(default)]
+            pub(crate) can_edit: bool,
+            #[serde(with = "super::timestamp")]
+            pub(crate) created_utc: SystemTime,
+        }
+
+        // Only the name is interesting, the rest of the subreddit data is only included on request.
+        #[derive(Debug, Deserialize)]
+        pub struct RawMultiredditSubreddit {
+            pub(crate) name: String,
+        }
+    }
+
+    pub mod message {
+        use std::time::SystemTime;
+
+        use serde::Deserialize;
+
+        use crate::things::Fullname;
+
+        // Private messages, but also comment replies and mentions as they appear in the inbox.
+        #[derive(Debug, Clone, Deserialize)]
+        pub struct RawMessageData {
             pub(crate) id: String,
+            pub(crate) name: Fullname,
+            pub(crate) author: Option<String>,
+            pub(crate) dest: String,
+            pub(crate) subject: String,
+            pub(crate) body: String,
+            #[serde(with = "super::timestamp")]
+            pub(crate) created_utc: SystemTime,
+            pub(crate) new: bool,
+            pub(crate) was_comment: bool,
+            #[serde(default)]
+            pub(crate) context: String,
+            pub(crate) parent_id: Option<Fullname>,
+            pub(crate) first_message_name: Option<Fullname>,
+            pub(crate) subreddit: Option<String>,
+            pub(crate) distinguished: Option<String>,
         }
     }
 }
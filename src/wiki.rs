@@ -0,0 +1,291 @@
+//! The wikis of subreddits.
+use std::time::SystemTime;
+
+use serde::Deserialize;
+
+use crate::{
+    auth::{AuthenticatedClient, Authenticator},
+    reddit::{check_api_errors, Error, Result},
+    things::{
+        raw::{
+            generic_kind::RawKind,
+            wiki::{RawWikiPage, RawWikiPageSettings, RawWikiRevision},
+        },
+        Feed, FromListing,
+    },
+};
+
+/// A handle to the wiki of a subreddit. Use [`crate::things::Subreddit::wiki`] to create one.
+/// Pages are identified by their name, e.g. `index` or `config/sidebar`.
+/// ```no_run
+/// # fn main() -> snew::reddit::Result<()> {
+/// # use snew::{reddit::Reddit, auth::{ScriptAuthenticator, Credentials}};
+/// # let reddit = Reddit::new(
+/// #    ScriptAuthenticator::new(Credentials::new("client_id", "client_secret", "username", "password")),
+/// #    "<Operating system>:snew:v0.1.0 (by u/<reddit username>)"
+/// #    ).unwrap();
+/// let wiki = reddit.subreddit("mysubreddit").wiki();
+///
+/// let faq = wiki.page("faq")?;
+/// let content = format!("{}\n\n* New question?\n\n  New answer.", faq.content_md);
+///
+/// wiki.edit("faq", &content, Some("Add new question"))?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct SubredditWiki<T: Authenticator> {
+    /// The URL of the subreddit.
+    pub url: String,
+    client: AuthenticatedClient<T>,
+}
+
+impl<T: Authenticator> SubredditWiki<T> {
+    /// Create a instance of a subreddit wiki
+    /// Use [`crate::things::Subreddit::wiki()`] instead.
+    pub fn create(url: &str, client: &AuthenticatedClient<T>) -> Self {
+        Self {
+            url: String::from(url),
+            client: client.clone(),
+        }
+    }
+
+    /// The names of all pages in this wiki.
+    pub fn pages(&self) -> Result<Vec<String>> {
+        let text = self
+            .client
+            .get(&format!("{}/wiki/pages", self.url), None::<&()>)?
+            .text()?;
+
+        let raw: RawKind<Vec<String>> = serde_json::from_str(&text)?;
+
+        Ok(raw.data)
+    }
+
+    /// The current revision of a page.
+    pub fn page(&self, name: &str) -> Result<WikiPage> {
+        self.get_page(name, None)
+    }
+
+    /// A specific revision of a page. See [`Self::revisions`].
+    pub fn page_revision(&self, name: &str, revision_id: &str) -> Result<WikiPage> {
+        self.get_page(name, Some(revision_id))
+    }
+
+    /// Replace the content of a page, creating it if it does not exist.
+    pub fn edit(&self, name: &str, content: &str, reason: Option<&str>) -> Result<()> {
+        let mut form = vec![("page", name), ("content", content)];
+        form.extend(reason.map(|reason| ("reason", reason)));
+
+        self.post("api/wiki/edit", &form)
+    }
+
+    /// The revisions of a page, most recent first.
+    pub fn revisions(&self, name: &str) -> Feed<T, WikiRevision> {
+        Feed::new(
+            format!("{}/wiki/revisions/{}", self.url, name),
+            &self.client,
+        )
+    }
+
+    /// Revert a page to an earlier revision.
+    pub fn revert(&self, name: &str, revision_id: &str) -> Result<()> {
+        self.post(
+            "api/wiki/revert",
+            &[("page", name), ("revision", revision_id)],
+        )
+    }
+
+    /// Who can edit a page, and whether it is listed.
+    pub fn settings(&self, name: &str) -> Result<WikiPageSettings> {
+        let text = self
+            .client
+            .get(&format!("{}/wiki/settings/{}", self.url, name), None::<&()>)?
+            .text()?;
+
+        let raw: RawKind<RawWikiPageSettings> = serde_json::from_str(&text)?;
+
+        Ok(raw.data.into())
+    }
+
+    /// Change who can edit a page, and whether it is listed.
+    /// The editors of the page are not changed, see [`Self::add_editor`] and [`Self::remove_editor`].
+    /// Errors with [`Error::UnknownValueError`] if the permission level is [`WikiPermLevel::Other`].
+    pub fn update_settings(&self, name: &str, settings: &WikiPageSettings) -> Result<()> {
+        self.post(
+            &format!("wiki/settings/{}", name),
+            &settings_form(settings)?,
+        )
+    }
+
+    /// Allow a user to edit a page, if the page is restricted to approved editors.
+    pub fn add_editor(&self, name: &str, username: &str) -> Result<()> {
+        self.post(
+            "api/wiki/alloweditor/add",
+            &[("page", name), ("username", username)],
+        )
+    }
+
+    /// Stop allowing a user to edit a page.
+    pub fn remove_editor(&self, name: &str, username: &str) -> Result<()> {
+        self.post(
+            "api/wiki/alloweditor/del",
+            &[("page", name), ("username", username)],
+        )
+    }
+
+    fn get_page(&self, name: &str, revision_id: Option<&str>) -> Result<WikiPage> {
+        let url = format!("{}/wiki/{}", self.url, name);
+
+        let response = match revision_id {
+            Some(revision_id) => self.client.get(&url, Some(&[("v", revision_id)]))?,
+            None => self.client.get(&url, None::<&()>)?,
+        };
+
+        let raw: RawKind<RawWikiPage> = serde_json::from_str(&response.text()?)?;
+
+        Ok(raw.data.into())
+    }
+
+    fn post(&self, path: &str, form: &[(&str, &str)]) -> Result<()> {
+        self.client.ensure_user()?;
+
+        let text = self
+            .client
+            .post(&format!("{}/{}", self.url, path), form)?
+            .text()?;
+
+        check_api_errors(&text)
+    }
+}
+
+/// A revision of a wiki page.
+#[derive(Debug, Clone)]
+pub struct WikiPage {
+    /// The content, in markdown.
+    pub content_md: String,
+    /// The content, as HTML.
+    pub content_html: String,
+    pub revision_id: Option<String>,
+    pub revision_date: Option<SystemTime>,
+    /// The user that made this revision.
+    pub revision_by: Option<String>,
+    /// The reason given for this revision.
+    pub reason: Option<String>,
+    /// Whether the logged in user can edit this page.
+    pub may_revise: bool,
+}
+
+impl From<RawWikiPage> for WikiPage {
+    fn from(raw: RawWikiPage) -> Self {
+        Self {
+            content_md: raw.content_md,
+            content_html: raw.content_html,
+            revision_id: raw.revision_id,
+            revision_date: raw.revision_date,
+            revision_by: raw.revision_by.map(|user| user.data.name),
+            reason: raw.reason,
+            may_revise: raw.may_revise,
+        }
+    }
+}
+
+/// An entry in the revision history of a wiki page. See [`SubredditWiki::revisions`].
+#[derive(Debug, Clone)]
+pub struct WikiRevision {
+    pub id: String,
+    /// The name of the page.
+    pub page: String,
+    pub timestamp: SystemTime,
+    pub reason: Option<String>,
+    /// The user that made this revision. None if the user is deleted.
+    pub author: Option<String>,
+    /// Whether this revision is hidden from the page history.
+    pub revision_hidden: bool,
+}
+
+impl<T: Authenticator> FromListing<T> for WikiRevision {
+    // Revision listings do not wrap their children in a kind.
+    type Raw = RawWikiRevision;
+
+    fn from_raw(raw: Self::Raw, _: &AuthenticatedClient<T>) -> Result<Self> {
+        Ok(Self {
+            id: raw.id,
+            page: raw.page,
+            timestamp: raw.timestamp,
+            reason: raw.reason,
+            author: raw.author.map(|user| user.data.name),
+            revision_hidden: raw.revision_hidden,
+        })
+    }
+}
+
+/// Who can edit a wiki page, and whether it is listed. See [`SubredditWiki::settings`].
+#[derive(Debug, Clone)]
+pub struct WikiPageSettings {
+    pub permlevel: WikiPermLevel,
+    /// Whether the page is included in the list of pages.
+    pub listed: bool,
+    /// The users allowed to edit the page, if it is restricted to approved editors.
+    pub editors: Vec<String>,
+}
+
+impl From<RawWikiPageSettings> for WikiPageSettings {
+    fn from(raw: RawWikiPageSettings) -> Self {
+        Self {
+            permlevel: raw.permlevel,
+            listed: raw.listed,
+            editors: raw.editors.into_iter().map(|user| user.data.name).collect(),
+        }
+    }
+}
+
+/// Who can edit a wiki page.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(from = "i32")]
+pub enum WikiPermLevel {
+    /// Use the wiki settings of the subreddit.
+    Inherit,
+    /// Only approved editors of the page, and moderators.
+    ApprovedEditors,
+    /// Only moderators.
+    ModsOnly,
+    /// Some level not known to snew. Can not be sent back to Reddit.
+    Other(i32),
+}
+
+impl WikiPermLevel {
+    fn as_str(&self) -> Option<&'static str> {
+        match self {
+            Self::Inherit => Some("0"),
+            Self::ApprovedEditors => Some("1"),
+            Self::ModsOnly => Some("2"),
+            Self::Other(_) => None,
+        }
+    }
+}
+
+impl From<i32> for WikiPermLevel {
+    fn from(value: i32) -> Self {
+        match value {
+            0 => Self::Inherit,
+            1 => Self::ApprovedEditors,
+            2 => Self::ModsOnly,
+            _ => Self::Other(value),
+        }
+    }
+}
+
+// The form fields for changing the settings of a wiki page.
+pub(crate) fn settings_form(
+    settings: &WikiPageSettings,
+) -> Result<[(&'static str, &'static str); 2]> {
+    let permlevel = settings.permlevel.as_str().ok_or_else(|| {
+        Error::UnknownValueError(String::from("the wiki permission level is unknown"))
+    })?;
+
+    Ok([
+        ("permlevel", permlevel),
+        ("listed", if settings.listed { "true" } else { "false" }),
+    ])
+}